@@ -1,7 +1,9 @@
 //! Represent a concrete instance of an Image, before it is ran as a Container.
 
-use crate::container::{CreatedContainer, PendingContainer};
-use crate::image::Image;
+use crate::container::{CreatedContainer, PendingContainer, Retry, RunningContainer};
+use crate::image::{Image, Source};
+use crate::plan::{ContainerPlan, PortPlan};
+use crate::resources::RESOURCES;
 use crate::static_container::STATIC_CONTAINERS;
 use crate::waitfor::{NoWait, WaitFor};
 use crate::{DockerTestError, Network};
@@ -11,26 +13,34 @@ use bollard::{
         Config, CreateContainerOptions, InspectContainerOptions, NetworkingConfig,
         RemoveContainerOptions,
     },
-    models::HostConfig,
+    models::{HealthConfig, HostConfig},
     service::{EndpointSettings, PortBinding},
     Docker,
 };
 
-use futures::future::TryFutureExt;
+use futures::future::{BoxFuture, Future, TryFutureExt};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::{event, trace, Level};
 
 /// Specifies the starting policy of a container specification.
 ///
 /// - [StartPolicy::Strict] policy will enforce that the container is started in the order
-///     it was added to [DockerTest].
+///   it was added to [DockerTest]. This is equivalent to each strict container declaring
+///   a dependency on the previously added strict container.
 /// - [StartPolicy::Relaxed] policy will not enforce any ordering,
-///     all container specifications with a relaxed policy will be started concurrently.
-///     These are all started asynchrously started before the strict policy containers
-///     are started sequentially.
+///   all container specifications with a relaxed policy will be started concurrently,
+///   as soon as their explicitly declared dependencies are ready.
+///
+/// Explicit inter-container dependencies are preferred over the [StartPolicy::Strict] policy,
+/// and are declared through `depends_on` on the container specification.
 ///
 /// [DockerTest]: crate::DockerTest
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum StartPolicy {
     /// Concurrently start the Container with other Relaxed instances.
     Relaxed,
@@ -38,25 +48,191 @@ pub enum StartPolicy {
     Strict,
 }
 
+/// Specifies how a container that failed to become ready is retried.
+///
+/// Once the [WaitFor] condition of a container fails, the container is removed and created anew
+/// before it is started again. The test fails with a startup error only once every attempt has
+/// failed, reporting the error of each attempt.
+///
+/// The delay before each new attempt starts at `backoff`, and is doubled after each attempt.
+/// Retries are only performed for containers managed by the individual test, static
+/// containers are never retried.
+///
+/// [WaitFor]: crate::waitfor::WaitFor
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts to start the container, including the first attempt.
+    pub max_attempts: u32,
+    /// The delay before the second attempt.
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a [RetryPolicy] with the given maximum attempts and initial backoff.
+    pub fn new(max_attempts: u32, backoff: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            backoff,
+        }
+    }
+
+    /// The delay to await after the given number of failed attempts.
+    pub(crate) fn delay(&self, failed_attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failed_attempts.saturating_sub(1));
+        self.backoff.saturating_mul(factor)
+    }
+}
+
+/// Specifies the healthcheck of a container, overriding the `HEALTHCHECK` of its image.
+///
+/// The docker daemon runs the test command within the container every `interval`, and
+/// considers the container unhealthy once `retries` consecutive probes have failed.
+/// Unset durations and retries inherit the values of the image, or the daemon defaults.
+/// Await a healthy container through [HealthyWait].
+///
+/// [HealthyWait]: crate::waitfor::HealthyWait
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthCheck {
+    test: Vec<String>,
+    interval: Option<Duration>,
+    timeout: Option<Duration>,
+    retries: Option<u32>,
+    start_period: Option<Duration>,
+}
+
+impl HealthCheck {
+    /// Creates a [HealthCheck] executing the given command and arguments directly.
+    ///
+    /// The container is healthy if the command exits with exit code 0.
+    pub fn cmd<T: ToString>(cmd: Vec<T>) -> HealthCheck {
+        let test = std::iter::once("CMD".to_string())
+            .chain(cmd.iter().map(|c| c.to_string()))
+            .collect();
+        HealthCheck::with_test(test)
+    }
+
+    /// Creates a [HealthCheck] running the given command with the default shell of the
+    /// container, e.g., `pg_isready -U postgres || exit 1`.
+    ///
+    /// The container is healthy if the command exits with exit code 0.
+    pub fn shell<T: ToString>(command: T) -> HealthCheck {
+        HealthCheck::with_test(vec!["CMD-SHELL".to_string(), command.to_string()])
+    }
+
+    fn with_test(test: Vec<String>) -> HealthCheck {
+        HealthCheck {
+            test,
+            interval: None,
+            timeout: None,
+            retries: None,
+            start_period: None,
+        }
+    }
+
+    /// Sets the time between each probe, with millisecond precision.
+    pub fn with_interval(self, interval: Duration) -> HealthCheck {
+        HealthCheck {
+            interval: Some(interval),
+            ..self
+        }
+    }
+
+    /// Sets the time a single probe may run before it is considered failed, with millisecond
+    /// precision.
+    pub fn with_timeout(self, timeout: Duration) -> HealthCheck {
+        HealthCheck {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the number of consecutive failed probes required to consider the container
+    /// unhealthy.
+    pub fn with_retries(self, retries: u32) -> HealthCheck {
+        HealthCheck {
+            retries: Some(retries),
+            ..self
+        }
+    }
+
+    /// Sets the time the container is given to initialize, during which failed probes do not
+    /// count towards the retries.
+    pub fn with_start_period(self, start_period: Duration) -> HealthCheck {
+        HealthCheck {
+            start_period: Some(start_period),
+            ..self
+        }
+    }
+
+    /// Convert into the healthcheck configuration of the docker daemon.
+    pub(crate) fn config(&self) -> HealthConfig {
+        // The daemon rejects non-zero durations below one millisecond.
+        let nanos =
+            |d: Option<Duration>| d.map(|d| d.max(Duration::from_millis(1)).as_nanos() as i64);
+
+        HealthConfig {
+            test: Some(self.test.clone()),
+            interval: nanos(self.interval),
+            timeout: nanos(self.timeout),
+            retries: self.retries.map(i64::from),
+            start_period: nanos(self.start_period),
+        }
+    }
+}
+
+/// An async hook invoked with a container once it is ready, prior to the test body.
+#[derive(Clone)]
+pub(crate) struct OnReady(
+    Arc<dyn Fn(RunningContainer) -> BoxFuture<'static, Result<(), DockerTestError>> + Send + Sync>,
+);
+
+impl OnReady {
+    pub(crate) fn new<F, Fut, E>(hook: F) -> OnReady
+    where
+        F: Fn(RunningContainer) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        OnReady(Arc::new(move |container| {
+            let fut = hook(container);
+            Box::pin(async move {
+                fut.await
+                    .map_err(|e| DockerTestError::Startup(format!("on_ready hook failed: {}", e)))
+            })
+        }))
+    }
+
+    /// Invoke the hook with the given container.
+    pub(crate) async fn call(&self, container: RunningContainer) -> Result<(), DockerTestError> {
+        (self.0)(container).await
+    }
+}
+
+impl std::fmt::Debug for OnReady {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OnReady")
+    }
+}
+
 /// Specifies who is responsible for managing a static container.
 ///
 /// - [StaticManagementPolicy::External] indicates that the user is responsible for managing the
-///     container, DockerTest will never start or remove/stop the container. The container will
-///     be available through its handle in [DockerOperations]. If no external network is
-///     supplied, the test-scoped network will be added to the external network, and subsequently
-///     removed once the test terminates.
-///     The externally managed container is assumed to be in a running state when the test starts.
-///     If DockerTest cannot locate the the container, the test will fail.
+///   container, DockerTest will never start or remove/stop the container. The container will
+///   be available through its handle in [DockerOperations]. If no external network is
+///   supplied, the test-scoped network will be added to the external network, and subsequently
+///   removed once the test terminates.
+///   The externally managed container is assumed to be in a running state when the test starts.
+///   If DockerTest cannot locate the the container, the test will fail.
 /// - [StaticManagementPolicy::Internal] indicates that DockerTest will handle the lifecycle of
-///     the container between all DockerTest instances within the test binary.
+///   the container between all DockerTest instances within the test binary.
 /// - [StaticManagementPolicy::Dynamic] indicates that DockerTest will start the
-///     container if it does not already exists and will not clean it up. This way the same
-///     container can be re-used across multiple `cargo test` invocations.
-///     If the `DOCKERTEST_DYNAMIC` environment variable is set to `INTERNAL` or `EXTERNAL`, the management policy
-///     will instead be set accordingly (either [StaticManagementPolicy::Internal] or [StaticManagementPolicy::External].
-///     The purpose of this is to facilitate running tests locally and in CI/CD pipelines without having to alter management policies.
-///     If a container already exists in a non-running state with the same name as a container with this policy, the startup
-///     procedure will fail.
+///   container if it does not already exists and will not clean it up. This way the same
+///   container can be re-used across multiple `cargo test` invocations.
+///   If the `DOCKERTEST_DYNAMIC` environment variable is set to `INTERNAL` or `EXTERNAL`, the management policy
+///   will instead be set accordingly (either [StaticManagementPolicy::Internal] or [StaticManagementPolicy::External].
+///   The purpose of this is to facilitate running tests locally and in CI/CD pipelines without having to alter management policies.
+///   If a container already exists in a non-running state with the same name as a container with this policy, the startup
+///   procedure will fail.
 ///
 /// [DockerOperations]: crate::DockerOperations
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -166,6 +342,18 @@ pub struct Composition {
     /// A trait object holding the implementation that indicate container readiness.
     wait: Box<dyn WaitFor>,
 
+    /// The deadline for this container to become ready, measured from when it is started.
+    startup_timeout: Option<Duration>,
+
+    /// How to retry the container if it fails to become ready.
+    retry_policy: Option<RetryPolicy>,
+
+    /// The hook to invoke once the container is ready and inspected.
+    pub(crate) on_ready: Option<OnReady>,
+
+    /// The healthcheck overriding the one of the image, if any.
+    healthcheck: Option<HealthCheck>,
+
     /// The environmentable variables that will be passed to the container.
     pub(crate) env: HashMap<String, String>,
 
@@ -173,7 +361,10 @@ pub struct Composition {
     cmd: Vec<String>,
 
     /// The start policy of this container, codifing the inter-depdencies between containers.
-    start_policy: StartPolicy,
+    pub(crate) start_policy: StartPolicy,
+
+    /// Handles of the containers that must be ready before this container is started.
+    pub(crate) dependencies: Vec<String>,
 
     /// The base image that will be the container we will be starting.
     image: Image,
@@ -200,7 +391,7 @@ pub struct Composition {
     pub(crate) inject_container_name_env: Vec<(String, String)>,
 
     /// Port mapping (used for Windows-compatibility)
-    port: Vec<(String, String)>,
+    pub(crate) port: Vec<(String, String)>,
 
     /// Allocates an ephemeral host port for all of a container’s exposed ports.
     ///
@@ -245,9 +436,14 @@ impl Composition {
             image: Image::with_repository(&copy),
             container_name: copy.replace('/', "-"),
             wait: Box::new(NoWait {}),
+            startup_timeout: None,
+            retry_policy: None,
+            on_ready: None,
+            healthcheck: None,
             env: HashMap::new(),
             cmd: Vec::new(),
             start_policy: StartPolicy::Relaxed,
+            dependencies: Vec::new(),
             bind_mounts: Vec::new(),
             named_volumes: Vec::new(),
             inject_container_name_env: Vec::new(),
@@ -271,9 +467,14 @@ impl Composition {
             container_name: image.repository().to_string().replace('/', "-"),
             image,
             wait: Box::new(NoWait {}),
+            startup_timeout: None,
+            retry_policy: None,
+            on_ready: None,
+            healthcheck: None,
             env: HashMap::new(),
             cmd: Vec::new(),
             start_policy: StartPolicy::Relaxed,
+            dependencies: Vec::new(),
             bind_mounts: Vec::new(),
             named_volumes: Vec::new(),
             inject_container_name_env: Vec::new(),
@@ -296,6 +497,18 @@ impl Composition {
         }
     }
 
+    /// Declare that this container depends on the container identified by `handle`.
+    ///
+    /// This container will not be started before the [WaitFor] condition of the
+    /// container identified by `handle` has been fulfilled.
+    pub fn depends_on<T: ToString>(&mut self, handle: T) -> &mut Composition {
+        let handle = handle.to_string();
+        if !self.dependencies.contains(&handle) {
+            self.dependencies.push(handle);
+        }
+        self
+    }
+
     /// Assigns the full set of environmental variables available for the [RunningContainer].
     ///
     /// Each key in the map should be the environmental variable name
@@ -386,6 +599,40 @@ impl Composition {
         Composition { wait, ..self }
     }
 
+    /// Sets the deadline for this `Composition` to become ready once started.
+    ///
+    /// The `WaitFor` operation is cancelled once the deadline expires.
+    pub fn with_startup_timeout(self, timeout: Duration) -> Composition {
+        Composition {
+            startup_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the [RetryPolicy] applied when this `Composition` fails to become ready.
+    pub fn with_retry_policy(self, retry_policy: RetryPolicy) -> Composition {
+        Composition {
+            retry_policy: Some(retry_policy),
+            ..self
+        }
+    }
+
+    /// Sets the hook invoked once this `Composition` is ready, prior to the test body.
+    pub(crate) fn with_on_ready(self, on_ready: OnReady) -> Composition {
+        Composition {
+            on_ready: Some(on_ready),
+            ..self
+        }
+    }
+
+    /// Sets the [HealthCheck] of this `Composition`, overriding the healthcheck of its image.
+    pub fn with_healthcheck(self, healthcheck: HealthCheck) -> Composition {
+        Composition {
+            healthcheck: Some(healthcheck),
+            ..self
+        }
+    }
+
     /// Sets log options for this `Composition`.
     /// By default `LogAction::Forward`, `LogPolicy::OnError`, and `LogSource::StdErr` is enabled.
     /// To clear default log option pass `None` or specify your own log options.
@@ -465,9 +712,10 @@ impl Composition {
     /// for connection details, and thus the DNS resolving capabilities within docker will
     /// map the container name into the correct IP address.
     ///
-    /// To correctly use this feature, the `StartPolicy` between the dependant containers
-    /// must be configured such that these connections can successfully be established.
-    /// Dockertest will not make any attempt to verify the integrity of these dependencies.
+    /// To correctly use this feature, the dependencies between the containers must be
+    /// declared through [Composition::depends_on] such that these connections can successfully
+    /// be established. Dockertest will not make any attempt to verify the integrity of these
+    /// dependencies.
     pub fn inject_container_name<T: ToString, E: ToString>(
         &mut self,
         handle: T,
@@ -580,6 +828,16 @@ impl Composition {
         let start_policy_clone = self.start_policy.clone();
         let container_name_clone = self.container_name.clone();
 
+        // Static containers are shared between tests, and are thus never recreated.
+        let retry = match &self.retry_policy {
+            Some(policy) if !self.is_static() => Some(Box::new(Retry {
+                policy: policy.clone(),
+                composition: self.clone(),
+                network: network.map(|n| n.to_string()),
+            })),
+            _ => None,
+        };
+
         if !self.is_static() {
             // Ensure we can remove the previous container instance, if it somehow still exists.
             // Only bail on non-recoverable failure.
@@ -687,6 +945,7 @@ impl Composition {
             networking_config: net_config,
             host_config,
             exposed_ports: Some(exposed_ports),
+            healthcheck: self.healthcheck.as_ref().map(HealthCheck::config),
             ..Default::default()
         };
 
@@ -697,17 +956,51 @@ impl Composition {
             .map_err(|e| DockerTestError::Daemon(format!("failed to create container: {}", e)))
             .await?;
 
+        // Dynamic containers are never removed by dockertest.
+        if self.management != Some(StaticManagementPolicy::Dynamic) {
+            RESOURCES.register_container(&container_info.id);
+        }
+
         let static_management_policy = self.static_management_policy().clone();
-        Ok(PendingContainer::new(
+        let mut pending = PendingContainer::new(
             &container_name_clone,
             container_info.id,
             self.handle(),
             start_policy_clone,
             self.wait,
+            self.startup_timeout,
+            retry,
             client.clone(),
             static_management_policy,
             self.log_options.clone(),
-        ))
+        );
+        pending.network = network.map(|n| n.to_string());
+        Ok(pending)
+    }
+
+    /// Describe this Composition as it would be created.
+    pub(crate) fn plan(&self, default_source: &Source) -> ContainerPlan {
+        ContainerPlan {
+            handle: self.handle(),
+            name: self.container_name.clone(),
+            image: self.image.plan(default_source),
+            start_policy: self.start_policy.clone(),
+            named_volumes: self.final_named_volume_names.clone(),
+            injected_env: self
+                .inject_container_name_env
+                .iter()
+                .filter_map(|(_, env)| self.env.get(env).map(|name| (env.clone(), name.clone())))
+                .collect(),
+            ports: self
+                .port
+                .iter()
+                .map(|(exposed, host)| PortPlan {
+                    exposed: exposed.clone(),
+                    host: host.clone(),
+                })
+                .collect(),
+            publish_all_ports: self.publish_all_ports,
+        }
     }
 
     // Returns the Image associated with this Composition.
@@ -745,3 +1038,39 @@ async fn remove_container_if_exists(client: &Docker, name: &str) -> Result<(), D
         .map_err(|e| DockerTestError::Daemon(format!("failed to remove existing container: {}", e)))
         .await
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::composition::HealthCheck;
+
+    use std::time::Duration;
+
+    #[test]
+    fn test_healthcheck_config() {
+        let config = HealthCheck::cmd(vec!["pg_isready", "-U", "postgres"])
+            .with_interval(Duration::from_millis(500))
+            .with_retries(3)
+            .with_start_period(Duration::from_nanos(1))
+            .config();
+
+        assert_eq!(
+            config.test,
+            Some(vec![
+                "CMD".to_string(),
+                "pg_isready".to_string(),
+                "-U".to_string(),
+                "postgres".to_string(),
+            ])
+        );
+        assert_eq!(config.interval, Some(500_000_000));
+        assert_eq!(config.timeout, None);
+        assert_eq!(config.retries, Some(3));
+        assert_eq!(config.start_period, Some(1_000_000));
+
+        let config = HealthCheck::shell("exit 1").config();
+        assert_eq!(
+            config.test,
+            Some(vec!["CMD-SHELL".to_string(), "exit 1".to_string()])
+        );
+    }
+}
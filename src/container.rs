@@ -1,13 +1,25 @@
 //! Represents the multiple phases and variants a docker container exists in dockertest.
 
 mod cleanup;
+mod copy;
+pub(crate) mod exec;
+mod lifecycle;
+pub(crate) mod logs;
 mod pending;
 mod running;
+mod state;
+mod stats;
 
 pub(crate) use cleanup::CleanupContainer;
+pub use exec::{ExecOptions, ExecOutput};
+pub use logs::{LogLine, LogStreamOptions};
 pub use pending::PendingContainer;
+pub(crate) use pending::{Retry, StartOutcome};
 pub(crate) use running::HostPortMappings;
 pub use running::RunningContainer;
+pub(crate) use state::unpause_if_paused;
+pub use state::{ContainerState, ContainerStatus, ExitStatus, HealthStatus};
+pub use stats::ContainerStats;
 
 /// Represents an exisiting static external container.
 ///
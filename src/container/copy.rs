@@ -0,0 +1,193 @@
+//! Copy files and directories into and out of a running container through tar archives.
+
+use crate::DockerTestError;
+
+use bollard::{
+    container::{DownloadFromContainerOptions, UploadToContainerOptions},
+    Docker,
+};
+use futures::TryStreamExt;
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Split an absolute container path into its parent directory and final component.
+fn split_container_path(path: &str) -> Result<(String, String), DockerTestError> {
+    let trimmed = path.trim_end_matches('/');
+    match trimmed.rsplit_once('/') {
+        Some((parent, name)) if path.starts_with('/') && !name.is_empty() => {
+            let parent = if parent.is_empty() { "/" } else { parent };
+            Ok((parent.to_string(), name.to_string()))
+        }
+        _ => Err(DockerTestError::Processing(format!(
+            "container path `{}` must be an absolute path to a file or directory",
+            path
+        ))),
+    }
+}
+
+/// Create a tar archive containing a single file with the given name and contents.
+fn archive_bytes(name: &str, contents: &[u8]) -> Result<Vec<u8>, DockerTestError> {
+    let mtime = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+
+    let mut builder = tar::Builder::new(Vec::new());
+    builder
+        .append_data(&mut header, name, contents)
+        .and_then(|_| builder.into_inner())
+        .map_err(|e| DockerTestError::Processing(format!("failed to create archive: {}", e)))
+}
+
+/// Create a tar archive of the host file or directory at `source`, renamed to `name`.
+fn archive_host_path(source: &Path, name: &str) -> Result<Vec<u8>, DockerTestError> {
+    let mut builder = tar::Builder::new(Vec::new());
+    let result = if source.is_dir() {
+        builder.append_dir_all(name, source)
+    } else {
+        builder.append_path_with_name(source, name)
+    };
+
+    result.and_then(|_| builder.into_inner()).map_err(|e| {
+        DockerTestError::Processing(format!("failed to archive `{}`: {}", source.display(), e))
+    })
+}
+
+/// Extract the contents of the single regular file within the given tar archive.
+fn extract_file(archive: &[u8], path: &str) -> Result<Vec<u8>, DockerTestError> {
+    let error = |e: std::io::Error| {
+        DockerTestError::Processing(format!("failed to read archive of `{}`: {}", path, e))
+    };
+
+    let mut archive = tar::Archive::new(archive);
+    let mut entries = archive.entries().map_err(error)?;
+    match entries.next() {
+        Some(entry) => {
+            let mut entry = entry.map_err(error)?;
+            if !entry.header().entry_type().is_file() {
+                return Err(DockerTestError::Processing(format!(
+                    "container path `{}` is not a regular file",
+                    path
+                )));
+            }
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents).map_err(error)?;
+            Ok(contents)
+        }
+        None => Err(DockerTestError::Processing(format!(
+            "archive of `{}` is empty",
+            path
+        ))),
+    }
+}
+
+/// Extract the tar archive into the given container directory.
+async fn upload(
+    client: &Docker,
+    id: &str,
+    directory: String,
+    archive: Vec<u8>,
+) -> Result<(), DockerTestError> {
+    let options = UploadToContainerOptions {
+        path: directory,
+        ..Default::default()
+    };
+
+    client
+        .upload_to_container(id, Some(options), archive.into())
+        .await
+        .map_err(|e| DockerTestError::Daemon(format!("failed to copy into container: {}", e)))
+}
+
+/// Copy the host file or directory at `source` to `destination` within the container.
+pub(crate) async fn copy_to(
+    client: &Docker,
+    id: &str,
+    source: PathBuf,
+    destination: &str,
+) -> Result<(), DockerTestError> {
+    let (directory, name) = split_container_path(destination)?;
+    let archive = tokio::task::spawn_blocking(move || archive_host_path(&source, &name))
+        .await
+        .map_err(|e| DockerTestError::Processing(format!("failed to archive: {}", e)))??;
+
+    upload(client, id, directory, archive).await
+}
+
+/// Write `contents` to the file at `destination` within the container.
+pub(crate) async fn write_file(
+    client: &Docker,
+    id: &str,
+    destination: &str,
+    contents: &[u8],
+) -> Result<(), DockerTestError> {
+    let (directory, name) = split_container_path(destination)?;
+    let archive = archive_bytes(&name, contents)?;
+
+    upload(client, id, directory, archive).await
+}
+
+/// Retrieve a tar archive of the file or directory at `source` within the container.
+pub(crate) async fn copy_from(
+    client: &Docker,
+    id: &str,
+    source: &str,
+) -> Result<Vec<u8>, DockerTestError> {
+    let options = DownloadFromContainerOptions { path: source };
+
+    client
+        .download_from_container(id, Some(options))
+        .try_fold(Vec::new(), |mut archive, chunk| async move {
+            archive.extend_from_slice(&chunk);
+            Ok(archive)
+        })
+        .await
+        .map_err(|e| {
+            DockerTestError::Daemon(format!("failed to copy `{}` from container: {}", source, e))
+        })
+}
+
+/// Read the contents of the file at `source` within the container.
+pub(crate) async fn read_file(
+    client: &Docker,
+    id: &str,
+    source: &str,
+) -> Result<Vec<u8>, DockerTestError> {
+    let archive = copy_from(client, id, source).await?;
+    extract_file(&archive, source)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::copy::{archive_bytes, extract_file, split_container_path};
+
+    // Container paths are split into the directory to extract into and the entry name.
+    #[test]
+    fn test_split_container_path() {
+        assert_eq!(
+            split_container_path("/etc/app/config.yml").unwrap(),
+            ("/etc/app".to_string(), "config.yml".to_string())
+        );
+        assert_eq!(
+            split_container_path("/fixtures/").unwrap(),
+            ("/".to_string(), "fixtures".to_string())
+        );
+        assert!(split_container_path("relative/path").is_err());
+        assert!(split_container_path("/").is_err());
+    }
+
+    // A single file survives the roundtrip through an archive.
+    #[test]
+    fn test_archive_roundtrip() {
+        let archive = archive_bytes("config.yml", b"key: value").unwrap();
+        let contents = extract_file(&archive, "/config.yml").unwrap();
+        assert_eq!(contents, b"key: value");
+    }
+}
@@ -0,0 +1,236 @@
+//! Execute commands within a running container.
+
+use crate::DockerTestError;
+
+use bollard::{
+    container::LogOutput,
+    exec::{CreateExecOptions, StartExecResults},
+    Docker,
+};
+use futures::StreamExt;
+use tokio::io::AsyncWriteExt;
+
+use std::time::Duration;
+
+/// The command, and its configuration, to execute within a [RunningContainer].
+///
+/// [RunningContainer]: crate::RunningContainer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecOptions {
+    cmd: Vec<String>,
+    env: Vec<String>,
+    workdir: Option<String>,
+    user: Option<String>,
+    stdin: Option<Vec<u8>>,
+    timeout: Option<Duration>,
+}
+
+/// The captured output of a command executed within a [RunningContainer].
+///
+/// [RunningContainer]: crate::RunningContainer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecOutput {
+    /// Everything the command wrote to stdout.
+    pub stdout: Vec<u8>,
+    /// Everything the command wrote to stderr.
+    pub stderr: Vec<u8>,
+    /// The exit code of the command.
+    pub exit_code: i64,
+}
+
+impl ExecOptions {
+    /// Creates the options to execute the given command, where the first element is the
+    /// executable and the remaining elements its arguments.
+    pub fn new<T: Into<String>>(cmd: Vec<T>) -> ExecOptions {
+        ExecOptions {
+            cmd: cmd.into_iter().map(|c| c.into()).collect(),
+            env: Vec::new(),
+            workdir: None,
+            user: None,
+            stdin: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets an environment variable for the executed command.
+    pub fn with_env<T: ToString, S: ToString>(mut self, name: T, value: S) -> ExecOptions {
+        self.env
+            .push(format!("{}={}", name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the working directory the command is executed within.
+    pub fn with_workdir<T: ToString>(mut self, workdir: T) -> ExecOptions {
+        self.workdir = Some(workdir.to_string());
+        self
+    }
+
+    /// Sets the user the command is executed as, on the form `user`, `user:group`, `uid` or
+    /// `uid:gid`.
+    pub fn with_user<T: ToString>(mut self, user: T) -> ExecOptions {
+        self.user = Some(user.to_string());
+        self
+    }
+
+    /// Writes the given bytes to the stdin of the command, which is closed afterwards.
+    pub fn with_stdin<T: Into<Vec<u8>>>(mut self, stdin: T) -> ExecOptions {
+        self.stdin = Some(stdin.into());
+        self
+    }
+
+    /// Sets the maximum duration to wait for the command to complete.
+    ///
+    /// Upon timing out, the command is left running within the container.
+    pub fn with_timeout(mut self, timeout: Duration) -> ExecOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn create_options(&self) -> CreateExecOptions<String> {
+        CreateExecOptions {
+            attach_stdin: Some(self.stdin.is_some()),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            env: Some(self.env.clone()),
+            cmd: Some(self.cmd.clone()),
+            user: self.user.clone(),
+            working_dir: self.workdir.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl ExecOutput {
+    /// Returns whether the command exited with exit code 0.
+    pub fn success(&self) -> bool {
+        self.exit_code == 0
+    }
+
+    /// Returns stdout as a string, replacing invalid UTF-8 sequences.
+    pub fn stdout_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stdout).into_owned()
+    }
+
+    /// Returns stderr as a string, replacing invalid UTF-8 sequences.
+    pub fn stderr_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).into_owned()
+    }
+}
+
+/// Execute the command described by `options` within the container `id`.
+pub(crate) async fn exec(
+    client: &Docker,
+    handle: &str,
+    id: &str,
+    options: ExecOptions,
+) -> Result<ExecOutput, DockerTestError> {
+    match options.timeout {
+        Some(timeout) => tokio::time::timeout(timeout, exec_inner(client, id, &options))
+            .await
+            .map_err(|_| DockerTestError::ExecTimeout {
+                handle: handle.to_string(),
+                cmd: options.cmd.join(" "),
+                timeout,
+            })?,
+        None => exec_inner(client, id, &options).await,
+    }
+}
+
+async fn exec_inner(
+    client: &Docker,
+    id: &str,
+    options: &ExecOptions,
+) -> Result<ExecOutput, DockerTestError> {
+    let created = client
+        .create_exec(id, options.create_options())
+        .await
+        .map_err(|e| DockerTestError::Daemon(format!("failed to create exec: {}", e)))?;
+
+    let (mut output, mut input) = match client.start_exec(&created.id, None).await {
+        Ok(StartExecResults::Attached { output, input }) => (output, input),
+        Ok(StartExecResults::Detached) => {
+            return Err(DockerTestError::Daemon(
+                "exec unexpectedly started detached".to_string(),
+            ))
+        }
+        Err(e) => {
+            return Err(DockerTestError::Daemon(format!(
+                "failed to start exec: {}",
+                e
+            )))
+        }
+    };
+
+    if let Some(stdin) = &options.stdin {
+        input
+            .write_all(stdin)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to write exec stdin: {}", e)))?;
+        input
+            .shutdown()
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to close exec stdin: {}", e)))?;
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    while let Some(chunk) = output.next().await {
+        match chunk {
+            Ok(LogOutput::StdErr { message }) => stderr.extend_from_slice(&message),
+            Ok(LogOutput::StdOut { message }) | Ok(LogOutput::Console { message }) => {
+                stdout.extend_from_slice(&message)
+            }
+            Ok(LogOutput::StdIn { .. }) => (),
+            Err(e) => {
+                return Err(DockerTestError::Daemon(format!(
+                    "failed to read exec output: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    let inspected = client
+        .inspect_exec(&created.id)
+        .await
+        .map_err(|e| DockerTestError::Daemon(format!("failed to inspect exec: {}", e)))?;
+    let exit_code = inspected.exit_code.ok_or_else(|| {
+        DockerTestError::Daemon("exec completed without an exit code".to_string())
+    })?;
+
+    Ok(ExecOutput {
+        stdout,
+        stderr,
+        exit_code,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::ExecOptions;
+
+    // The builder is translated into the daemon exec options.
+    #[test]
+    fn test_exec_options_translation() {
+        let options = ExecOptions::new(vec!["psql", "-c", "SELECT 1"])
+            .with_env("PGUSER", "postgres")
+            .with_workdir("/tmp")
+            .with_user("postgres")
+            .with_stdin("input");
+
+        let create = options.create_options();
+        assert_eq!(
+            create.cmd,
+            Some(vec![
+                "psql".to_string(),
+                "-c".to_string(),
+                "SELECT 1".to_string()
+            ])
+        );
+        assert_eq!(create.env, Some(vec!["PGUSER=postgres".to_string()]));
+        assert_eq!(create.working_dir, Some("/tmp".to_string()));
+        assert_eq!(create.user, Some("postgres".to_string()));
+        assert_eq!(create.attach_stdin, Some(true));
+        assert_eq!(create.attach_stdout, Some(true));
+    }
+}
@@ -0,0 +1,252 @@
+//! Control the lifecycle of a running container from within the test body.
+
+use crate::{
+    container::{logs, state, ExitStatus, PendingContainer, RunningContainer},
+    DockerTestError, StartPolicy,
+};
+
+use bollard::container::{KillContainerOptions, StartContainerOptions};
+use tracing::{event, Level};
+
+use std::time::Duration;
+
+impl RunningContainer {
+    /// Stop this container, waiting for the container to exit gracefully before it is killed.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn stop(&self) -> Result<(), DockerTestError> {
+        self.refuse_static("stop")?;
+
+        self.client
+            .stop_container(&self.id, None)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to stop container: {}", e)))?;
+
+        self.refresh().await
+    }
+
+    /// Start this container after it has been stopped or killed.
+    ///
+    /// The container is considered started once its `WaitFor` condition is fulfilled anew.
+    /// Log based conditions only observe the output emitted since the container was started.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn start(&self) -> Result<(), DockerTestError> {
+        self.refuse_static("start")?;
+
+        self.client
+            .start_container(&self.id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to start container: {}", e)))?;
+
+        self.await_ready().await?;
+        self.refresh().await
+    }
+
+    /// Restart this container.
+    ///
+    /// The container is considered restarted once its `WaitFor` condition is fulfilled anew,
+    /// after which [RunningContainer::ip] and [RunningContainer::host_port] reflect the
+    /// restarted container. Log based conditions only observe the output emitted since the
+    /// container was restarted.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn restart(&self) -> Result<(), DockerTestError> {
+        self.refuse_static("restart")?;
+
+        self.client
+            .restart_container(&self.id, None)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to restart container: {}", e)))?;
+
+        self.await_ready().await?;
+        self.refresh().await
+    }
+
+    /// Send the given signal to the main process of this container, e.g., `SIGKILL` or `SIGHUP`.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn kill(&self, signal: &str) -> Result<(), DockerTestError> {
+        self.refuse_static("kill")?;
+
+        self.client
+            .kill_container(&self.id, Some(KillContainerOptions { signal }))
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to kill container: {}", e)))?;
+
+        self.refresh().await
+    }
+
+    /// Await this container exiting, returning its exit code along with all of its log output.
+    ///
+    /// Returns immediately if the container has already exited, e.g., through [ExitedWait].
+    ///
+    /// [ExitedWait]: crate::waitfor::ExitedWait
+    pub async fn wait_exit(&self, timeout: Duration) -> Result<ExitStatus, DockerTestError> {
+        let code = tokio::time::timeout(timeout, state::wait_exit_code(&self.client, &self.id))
+            .await
+            .map_err(|_| DockerTestError::ExitTimeout {
+                handle: self.handle.clone(),
+                timeout,
+            })??;
+
+        let logs = logs::snapshot(&self.client, &self.id).await?;
+        Ok(ExitStatus { code, logs })
+    }
+
+    /// Pause all processes within this container, freezing it until it is unpaused.
+    ///
+    /// A container left paused by the test body is unpaused prior to teardown.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn pause(&self) -> Result<(), DockerTestError> {
+        self.refuse_static("pause")?;
+
+        self.client
+            .pause_container(&self.id)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to pause container: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Unpause all processes within this container.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn unpause(&self) -> Result<(), DockerTestError> {
+        self.refuse_static("unpause")?;
+
+        self.client
+            .unpause_container(&self.id)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to unpause container: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Pause this container for the given duration, unpausing it once the duration elapsed.
+    ///
+    /// The container is also unpaused if the returned future is dropped before the duration
+    /// elapsed, e.g., when racing it against a client request with `tokio::select!`.
+    ///
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn pause_for(&self, duration: Duration) -> Result<(), DockerTestError> {
+        self.pause().await?;
+
+        let guard = PauseGuard { container: self };
+        tokio::time::sleep(duration).await;
+        std::mem::forget(guard);
+
+        self.unpause().await
+    }
+
+    pub(crate) fn refuse_static(&self, operation: &str) -> Result<(), DockerTestError> {
+        if self.is_static {
+            Err(DockerTestError::StaticContainer {
+                handle: self.handle.clone(),
+                operation: operation.to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Await the `WaitFor` condition of this container, if any.
+    async fn await_ready(&self) -> Result<(), DockerTestError> {
+        let wait = match &self.wait {
+            Some(wait) => wait.clone(),
+            None => return Ok(()),
+        };
+
+        event!(
+            Level::DEBUG,
+            "awaiting readiness of restarted container `{}`",
+            self.handle
+        );
+        let mut pending = PendingContainer::new(
+            &self.name,
+            &self.id,
+            &self.handle,
+            StartPolicy::Relaxed,
+            wait.clone(),
+            None,
+            None,
+            self.client.clone(),
+            None,
+            self.log_options.clone(),
+        );
+        // Log output of previous runs shall not fulfill log based conditions.
+        pending.logs_since = state::inspect_state(&self.client, &self.id)
+            .await?
+            .started_at;
+        pending.network = self.inspected().network;
+        wait.wait_for_ready(pending).await.map(|_| ())
+    }
+}
+
+/// Unpauses the container in the background if [RunningContainer::pause_for] is cancelled.
+struct PauseGuard<'a> {
+    container: &'a RunningContainer,
+}
+
+impl Drop for PauseGuard<'_> {
+    // Without a runtime, or should the test end before the spawned unpause completes,
+    // the container is unpaused during teardown.
+    fn drop(&mut self) {
+        let container = self.container.clone();
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                if let Err(e) = container.client.unpause_container(&container.id).await {
+                    event!(Level::ERROR, "failed to unpause container: {}", e);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::composition::StaticManagementPolicy;
+    use crate::container::{PendingContainer, RunningContainer};
+    use crate::utils::connect_with_local_or_tls_defaults;
+    use crate::waitfor::NoWait;
+    use crate::{DockerTestError, StartPolicy};
+
+    use std::time::Duration;
+
+    // Static containers are shared between tests, and thus refuse lifecycle operations.
+    #[tokio::test]
+    async fn test_static_container_refuses_lifecycle_operations() {
+        let client = connect_with_local_or_tls_defaults().unwrap();
+        let container: RunningContainer = PendingContainer::new(
+            "static",
+            "id",
+            "static",
+            StartPolicy::Relaxed,
+            Box::new(NoWait {}),
+            None,
+            None,
+            client,
+            Some(StaticManagementPolicy::Internal),
+            None,
+        )
+        .into();
+
+        let expected = |operation: &str| {
+            Err(DockerTestError::StaticContainer {
+                handle: "static".to_string(),
+                operation: operation.to_string(),
+            })
+        };
+        assert_eq!(container.stop().await, expected("stop"));
+        assert_eq!(container.start().await, expected("start"));
+        assert_eq!(container.restart().await, expected("restart"));
+        assert_eq!(container.kill("SIGKILL").await, expected("kill"));
+        assert_eq!(container.pause().await, expected("pause"));
+        assert_eq!(container.unpause().await, expected("unpause"));
+        assert_eq!(
+            container.pause_for(Duration::from_secs(1)).await,
+            expected("pause")
+        );
+    }
+}
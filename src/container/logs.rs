@@ -0,0 +1,298 @@
+//! Read the log output of a running container line by line.
+
+use crate::{container::state::parse_timestamp, DockerTestError, LogSource};
+
+use bollard::{
+    container::{LogOutput, LogsOptions},
+    Docker,
+};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single line of log output of a container, without its trailing newline.
+///
+/// Invalid UTF-8 sequences within the line are replaced with the unicode replacement
+/// character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogLine {
+    /// A line written to stdout.
+    StdOut(String),
+    /// A line written to stderr.
+    StdErr(String),
+}
+
+/// Which log output of a [RunningContainer] to read, and how.
+///
+/// By default, all log output from both stdout and stderr emitted so far is read.
+///
+/// [RunningContainer]: crate::RunningContainer
+#[derive(Clone, Debug)]
+pub struct LogStreamOptions {
+    source: LogSource,
+    follow: bool,
+    since: Option<SystemTime>,
+    /// Whether `since` is applied with nanosecond precision, by reading the timestamp of each
+    /// line.
+    precise_since: bool,
+    until: Option<SystemTime>,
+    tail: Option<usize>,
+}
+
+impl LogLine {
+    /// Returns the content of this line.
+    pub fn message(&self) -> &str {
+        match self {
+            LogLine::StdOut(message) | LogLine::StdErr(message) => message,
+        }
+    }
+}
+
+impl Default for LogStreamOptions {
+    fn default() -> LogStreamOptions {
+        LogStreamOptions {
+            source: LogSource::Both,
+            follow: false,
+            since: None,
+            precise_since: false,
+            until: None,
+            tail: None,
+        }
+    }
+}
+
+impl LogStreamOptions {
+    /// Creates the options to read all log output emitted so far.
+    pub fn new() -> LogStreamOptions {
+        LogStreamOptions::default()
+    }
+
+    /// Sets which of stdout and stderr to read.
+    pub fn with_source(mut self, source: LogSource) -> LogStreamOptions {
+        self.source = source;
+        self
+    }
+
+    /// Keep the stream open, yielding new log output as it is emitted until the container
+    /// exits.
+    pub fn with_follow(mut self, follow: bool) -> LogStreamOptions {
+        self.follow = follow;
+        self
+    }
+
+    /// Only read log output emitted at or after the given point in time, with second precision.
+    pub fn with_since(mut self, since: SystemTime) -> LogStreamOptions {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only read log output emitted at or after the given point in time, with nanosecond
+    /// precision.
+    pub(crate) fn with_precise_since(mut self, since: SystemTime) -> LogStreamOptions {
+        self.since = Some(since);
+        self.precise_since = true;
+        self
+    }
+
+    /// Only read log output emitted before the given point in time, with second precision.
+    pub fn with_until(mut self, until: SystemTime) -> LogStreamOptions {
+        self.until = Some(until);
+        self
+    }
+
+    /// Only read the last `lines` lines of log output emitted so far.
+    pub fn with_tail(mut self, lines: usize) -> LogStreamOptions {
+        self.tail = Some(lines);
+        self
+    }
+
+    fn logs_options(&self) -> LogsOptions<String> {
+        let unix_secs = |time: Option<SystemTime>| {
+            time.and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default()
+        };
+
+        LogsOptions {
+            follow: self.follow,
+            stdout: matches!(self.source, LogSource::StdOut | LogSource::Both),
+            stderr: matches!(self.source, LogSource::StdErr | LogSource::Both),
+            since: unix_secs(self.since),
+            until: unix_secs(self.until),
+            timestamps: self.precise_since,
+            tail: self
+                .tail
+                .map(|t| t.to_string())
+                .unwrap_or_else(|| "all".to_string()),
+        }
+    }
+}
+
+/// Reassembles lines of log output from arbitrarily split chunks, separately for each source.
+#[derive(Default)]
+pub(crate) struct LineSplitter {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+impl LineSplitter {
+    /// Append a chunk of log output, returning all lines it completed.
+    pub(crate) fn push(&mut self, output: LogOutput) -> Vec<LogLine> {
+        let (buffer, line): (_, fn(String) -> LogLine) = match output {
+            LogOutput::StdOut { message } | LogOutput::Console { message } => {
+                self.stdout.extend_from_slice(&message);
+                (&mut self.stdout, LogLine::StdOut)
+            }
+            LogOutput::StdErr { message } => {
+                self.stderr.extend_from_slice(&message);
+                (&mut self.stderr, LogLine::StdErr)
+            }
+            LogOutput::StdIn { .. } => return Vec::new(),
+        };
+
+        let mut lines = Vec::new();
+        while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+            let raw: Vec<u8> = buffer.drain(..=end).collect();
+            lines.push(line(decode_line(&raw)));
+        }
+        lines
+    }
+
+    /// Returns the trailing lines not terminated by a newline.
+    pub(crate) fn finish(&mut self) -> Vec<LogLine> {
+        let mut lines = Vec::new();
+        if !self.stdout.is_empty() {
+            lines.push(LogLine::StdOut(decode_line(&self.stdout)));
+            self.stdout.clear();
+        }
+        if !self.stderr.is_empty() {
+            lines.push(LogLine::StdErr(decode_line(&self.stderr)));
+            self.stderr.clear();
+        }
+        lines
+    }
+}
+
+fn decode_line(raw: &[u8]) -> String {
+    let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+    let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+    String::from_utf8_lossy(raw).into_owned()
+}
+
+/// Stream the log output of the container `id` line by line.
+pub(crate) fn logs(
+    client: &Docker,
+    id: &str,
+    options: &LogStreamOptions,
+) -> BoxStream<'static, Result<LogLine, DockerTestError>> {
+    let since = options.since.filter(|_| options.precise_since);
+    let chunks = client
+        .logs(id, Some(options.logs_options()))
+        .map(Some)
+        .chain(stream::once(async { None }));
+
+    chunks
+        .scan(LineSplitter::default(), |splitter, chunk| {
+            let lines = match chunk {
+                Some(Ok(output)) => splitter.push(output).into_iter().map(Ok).collect(),
+                Some(Err(e)) => vec![Err(DockerTestError::Daemon(format!(
+                    "failed to read container logs: {}",
+                    e
+                )))],
+                None => splitter.finish().into_iter().map(Ok).collect(),
+            };
+            futures::future::ready(Some(stream::iter(lines)))
+        })
+        .flatten()
+        .filter_map(move |line| {
+            futures::future::ready(match (line, since) {
+                (Ok(line), Some(since)) => emitted_since(line, since).map(Ok),
+                (line, _) => Some(line),
+            })
+        })
+        .boxed()
+}
+
+/// Strip the timestamp prefixed to the line by the docker daemon, discarding the line if it
+/// was emitted before `since`.
+///
+/// Lines without a timestamp are kept as is.
+fn emitted_since(line: LogLine, since: SystemTime) -> Option<LogLine> {
+    let (timestamp, message) = line
+        .message()
+        .split_once(' ')
+        .unwrap_or((line.message(), ""));
+    match parse_timestamp(timestamp) {
+        Some(emitted) if emitted < since => None,
+        Some(_) => {
+            let message = message.to_string();
+            Some(match line {
+                LogLine::StdOut(_) => LogLine::StdOut(message),
+                LogLine::StdErr(_) => LogLine::StdErr(message),
+            })
+        }
+        None => Some(line),
+    }
+}
+
+/// Retrieve all log output emitted so far by the container `id`.
+pub(crate) async fn snapshot(client: &Docker, id: &str) -> Result<Vec<LogLine>, DockerTestError> {
+    logs(client, id, &LogStreamOptions::default())
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::logs::{emitted_since, LineSplitter, LogLine};
+
+    use bollard::container::LogOutput;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    // Lines split across chunks are reassembled separately for each source.
+    #[test]
+    fn test_line_splitter_reassembles_chunks() {
+        let mut splitter = LineSplitter::default();
+        let stdout = |m: &'static [u8]| LogOutput::StdOut { message: m.into() };
+        let stderr = |m: &'static [u8]| LogOutput::StdErr { message: m.into() };
+
+        assert_eq!(splitter.push(stdout(b"hello ")), vec![]);
+        assert_eq!(
+            splitter.push(stderr(b"warn\r\nerr")),
+            vec![LogLine::StdErr("warn".to_string())]
+        );
+        assert_eq!(
+            splitter.push(stdout(b"world\nnext\n\xff\n")),
+            vec![
+                LogLine::StdOut("hello world".to_string()),
+                LogLine::StdOut("next".to_string()),
+                LogLine::StdOut("\u{FFFD}".to_string()),
+            ]
+        );
+        assert_eq!(splitter.finish(), vec![LogLine::StdErr("err".to_string())]);
+    }
+
+    // Lines emitted before the given point in time are discarded, with nanosecond precision.
+    #[test]
+    fn test_emitted_since() {
+        let since = UNIX_EPOCH + Duration::new(60, 500_000_000);
+        let stdout = |m: &str| LogLine::StdOut(m.to_string());
+
+        assert_eq!(
+            emitted_since(stdout("1970-01-01T00:01:00.4Z ready"), since),
+            None
+        );
+        assert_eq!(
+            emitted_since(stdout("1970-01-01T00:01:00.5Z ready to serve"), since),
+            Some(stdout("ready to serve"))
+        );
+        assert_eq!(
+            emitted_since(LogLine::StdErr("1970-01-01T00:01:01Z".to_string()), since),
+            Some(LogLine::StdErr(String::new()))
+        );
+        assert_eq!(
+            emitted_since(stdout("no timestamp"), since),
+            Some(stdout("no timestamp"))
+        );
+    }
+}
@@ -1,14 +1,24 @@
 //! Represents a created container, in transit to become a RunningContainer.
 
 use crate::{
-    composition::{LogOptions, StaticManagementPolicy},
+    composition::{Composition, LogOptions, RetryPolicy, StaticManagementPolicy},
     container::RunningContainer,
+    resources::RESOURCES,
     static_container::STATIC_CONTAINERS,
     waitfor::WaitFor,
     DockerTestError, StartPolicy,
 };
 
 use bollard::{container::StartContainerOptions, errors::Error, Docker};
+use tokio::time::Instant;
+use tracing::{event, Level};
+
+use std::time::{Duration, SystemTime};
+
+/// The outcome of starting a container, where a container that failed to start is handed
+/// back alongside the error, if it still exists.
+pub(crate) type StartOutcome =
+    Result<RunningContainer, (Option<PendingContainer>, DockerTestError)>;
 
 /// Represent a docker container object in a pending phase between
 /// it being created on the daemon, but may not be running.
@@ -37,6 +47,12 @@ pub struct PendingContainer {
     /// Trait implementing how to wait for the container to startup.
     pub(crate) wait: Option<Box<dyn WaitFor>>,
 
+    /// The deadline for this container to become ready, measured from when it is started.
+    pub(crate) startup_timeout: Option<Duration>,
+
+    /// How to recreate this container if it fails to become ready.
+    pub(crate) retry: Option<Box<Retry>>,
+
     /// Wheter this is a static container
     pub(crate) is_static: bool,
 
@@ -45,6 +61,13 @@ pub struct PendingContainer {
 
     /// Container log options, they are provided by `Composition`.
     pub(crate) log_options: Option<LogOptions>,
+
+    /// Log based `WaitFor` conditions only observe log output emitted at or after this point in
+    /// time, such that a restarted container does not observe the output of its previous run.
+    pub(crate) logs_since: Option<SystemTime>,
+
+    /// The test network this container is attached to, referenced by either its name or id.
+    pub(crate) network: Option<String>,
 }
 
 impl PendingContainer {
@@ -57,6 +80,8 @@ impl PendingContainer {
         handle: H,
         start_policy: StartPolicy,
         wait: Box<dyn WaitFor>,
+        startup_timeout: Option<Duration>,
+        retry: Option<Box<Retry>>,
         client: Docker,
         static_management_policy: Option<StaticManagementPolicy>,
         log_options: Option<LogOptions>,
@@ -67,10 +92,14 @@ impl PendingContainer {
             id: id.to_string(),
             handle: handle.to_string(),
             wait: Some(wait),
+            startup_timeout,
+            retry,
             start_policy,
             is_static: static_management_policy.is_some(),
             static_management_policy,
             log_options,
+            logs_since: None,
+            network: None,
         }
     }
 
@@ -85,6 +114,118 @@ impl PendingContainer {
         }
     }
 
+    /// Start the container in accordance with its [RetryPolicy], bounded by the global deadline.
+    ///
+    /// Each attempt is bounded by the earliest of the container's own startup timeout and the
+    /// global deadline. Once an attempt fails, the container is removed and created anew.
+    /// On failure, the container of the last attempt is returned alongside the error,
+    /// such that it is subject to the regular teardown procedure. If recreating the container
+    /// failed, no container remains and none is returned.
+    ///
+    /// The point in time each retry attempt began is returned alongside the outcome.
+    pub(crate) async fn start_with_retry(
+        self,
+        deadline: Option<(Instant, Duration)>,
+    ) -> (Vec<SystemTime>, StartOutcome) {
+        let mut pending = self;
+        let mut attempts = Vec::new();
+        let mut retries = Vec::new();
+
+        loop {
+            match pending.clone().start_within(deadline).await {
+                Ok(running) => return (retries, Ok(running)),
+                Err(e) => attempts.push(e),
+            }
+
+            let retry = match pending.retry.clone() {
+                Some(r) if (attempts.len() as u32) < r.policy.max_attempts => r,
+                _ => break,
+            };
+
+            let delay = retry.policy.delay(attempts.len() as u32);
+            if deadline.is_some_and(|(d, _)| Instant::now() + delay >= d) {
+                event!(
+                    Level::DEBUG,
+                    "global startup deadline prevents retrying container `{}`",
+                    pending.handle
+                );
+                break;
+            }
+
+            event!(
+                Level::WARN,
+                "container `{}` failed attempt {}, retrying in {:?}",
+                pending.handle,
+                attempts.len(),
+                delay
+            );
+            tokio::time::sleep(delay).await;
+            retries.push(SystemTime::now());
+
+            // Creation removes the failed container, as it shares the name of its replacement.
+            let recreated = retry
+                .composition
+                .create_inner(&pending.client, retry.network.as_deref())
+                .await;
+            RESOURCES.release_container(&pending.id);
+            match recreated {
+                Ok(recreated) => pending = recreated,
+                Err(e) => {
+                    attempts.push(e);
+                    return (retries, Err((None, Self::attempts_error(attempts))));
+                }
+            }
+        }
+
+        (
+            retries,
+            Err((Some(pending), Self::attempts_error(attempts))),
+        )
+    }
+
+    // Implementation detail
+    // A single failed attempt is reported as is.
+    fn attempts_error(mut attempts: Vec<DockerTestError>) -> DockerTestError {
+        if attempts.len() == 1 {
+            attempts.remove(0)
+        } else {
+            DockerTestError::StartupAttempts(attempts)
+        }
+    }
+
+    // Implementation detail
+    // On expiry of the deadline, the start future is dropped, which cancels the WaitFor operation.
+    async fn start_within(
+        self,
+        deadline: Option<(Instant, Duration)>,
+    ) -> Result<RunningContainer, DockerTestError> {
+        let own_deadline = self
+            .startup_timeout
+            .map(|t| (Instant::now() + t, t, "container"));
+        let global_deadline = deadline.map(|(d, t)| (d, t, "global"));
+        let deadline = match (own_deadline, global_deadline) {
+            (Some(own), Some(global)) => Some(if own.0 <= global.0 { own } else { global }),
+            (own, global) => own.or(global),
+        };
+
+        match deadline {
+            None => self.start().await,
+            Some((deadline, timeout, kind)) => {
+                let handle = self.handle.clone();
+                match tokio::time::timeout_at(deadline, self.start()).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        event!(Level::WARN, "container `{}` startup timed out", handle);
+                        Err(DockerTestError::Startup(format!(
+                            "container `{}` was not ready within the {} startup timeout of {:?}",
+                            handle, kind, timeout
+                        )))
+                    }
+                }
+            }
+        }
+    }
+
     /// Internal start method should only be invoked from the static mod.
     pub(crate) async fn start_internal(mut self) -> Result<RunningContainer, DockerTestError> {
         self.client
@@ -118,11 +259,22 @@ impl PendingContainer {
         let waitfor = self.wait.take().unwrap();
 
         // Issue WaitFor operation
-        let res = waitfor.wait_for_ready(self);
-        res.await
+        let mut running = waitfor.wait_for_ready(self).await?;
+
+        // Retain the WaitFor condition such that it can be awaited again on restarts.
+        running.wait = Some(waitfor);
+        Ok(running)
     }
 }
 
+/// Everything required to recreate a container for another start attempt.
+#[derive(Clone)]
+pub(crate) struct Retry {
+    pub(crate) policy: RetryPolicy,
+    pub(crate) composition: Composition,
+    pub(crate) network: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::container::PendingContainer;
@@ -144,6 +296,8 @@ mod tests {
             handle_key,
             StartPolicy::Relaxed,
             Box::new(NoWait {}),
+            None,
+            None,
             client,
             None,
             None,
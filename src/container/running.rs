@@ -2,21 +2,30 @@
 
 use crate::{
     composition::LogOptions,
-    container::PendingContainer,
-    waitfor::{wait_for_message, MessageSource},
+    container::{
+        copy, exec::exec, logs, state, stats, ContainerState, ContainerStats, ExecOptions,
+        ExecOutput, LogLine, LogStreamOptions, PendingContainer,
+    },
+    waitfor::{self, LogMatcher, MessageSource, WaitFor},
+    DockerTestError,
 };
 
 use bollard::{
+    container::InspectContainerOptions,
     models::{PortBinding, PortMap},
     Docker,
 };
+use futures::stream::BoxStream;
 use serde::Serialize;
+use tracing::{event, Level};
 
 use std::{
     collections::HashMap,
     convert::TryFrom,
     net::{IpAddr, Ipv4Addr},
+    path::Path,
     str::FromStr,
+    sync::{Arc, RwLock},
 };
 
 /// Represent a docker container in running state and available to the test body.
@@ -30,12 +39,34 @@ pub struct RunningContainer {
     pub(crate) id: String,
     /// The generated docker name for this running container.
     pub(crate) name: String,
+    /// Runtime details of the container, shared between all clones of this container within a
+    /// test such that they all observe the latest inspection.
+    pub(crate) inspected: Arc<RwLock<Inspected>>,
+    pub(crate) is_static: bool,
+    pub(crate) log_options: Option<LogOptions>,
+    /// The WaitFor condition to await whenever the container is started anew.
+    pub(crate) wait: Option<Box<dyn WaitFor>>,
+}
+
+/// The details of a container retrieved by inspecting it on the docker daemon.
+#[derive(Clone, Debug)]
+pub(crate) struct Inspected {
+    /// The network the container was inspected on.
+    pub(crate) network: Option<String>,
     /// IP address of the container
     pub(crate) ip: std::net::Ipv4Addr,
     /// Published container ports
     pub(crate) ports: HostPortMappings,
-    pub(crate) is_static: bool,
-    pub(crate) log_options: Option<LogOptions>,
+}
+
+impl Default for Inspected {
+    fn default() -> Inspected {
+        Inspected {
+            network: None,
+            ip: Ipv4Addr::UNSPECIFIED,
+            ports: HostPortMappings::default(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -82,6 +113,13 @@ impl TryFrom<PortMap> for HostPortMappings {
     }
 }
 
+impl HostPortMappings {
+    /// Returns the host ip/port binding for the given container port.
+    pub(crate) fn get(&self, exposed_port: u32) -> Option<(Ipv4Addr, u32)> {
+        self.mappings.get(&exposed_port).copied()
+    }
+}
+
 fn from_port_binding(ports: PortBinding) -> Result<Option<(Ipv4Addr, u32)>, HostPortMappingError> {
     match (ports.host_ip, ports.host_port) {
         (Some(ip), Some(port)) => {
@@ -119,9 +157,10 @@ impl RunningContainer {
     /// Use this address to contact the `RunningContainer` in the test body.
     ///
     /// This property is retrieved from the docker daemon prior to entering the test body.
-    /// It is cached internally and not updated between invocations. This means that
-    /// if the docker container enters an exited state, this function will still return
-    /// the original ip assigned to the container.
+    /// It is cached internally and only updated when the container is stopped, started,
+    /// restarted or killed through this `RunningContainer`, or explicitly through
+    /// [RunningContainer::refresh]. This means that if the docker container otherwise enters an
+    /// exited state, this function will still return the original ip assigned to the container.
     ///
     /// If the [ExitedWait] for strategy is employed, the `RunningContainer` will, somewhat
     /// contradictory to its name, be in an exited status when the test body
@@ -132,19 +171,128 @@ impl RunningContainer {
     ///
     /// [Ipv4Addr::UNSPECIFIED]: https://doc.rust-lang.org/std/net/struct.Ipv4Addr.html#associatedconstant.UNSPECIFIED
     /// [ExitedWait]: crate::waitfor::ExitedWait
-    pub fn ip(&self) -> &std::net::Ipv4Addr {
-        &self.ip
+    pub fn ip(&self) -> std::net::Ipv4Addr {
+        self.inspected().ip
     }
 
     /// Returns host ip/port binding for the given container port. Useful in MacOS where there is no
     /// network connectivity between Mac system and containers.
-    pub fn host_port(&self, exposed_port: u32) -> Option<&(Ipv4Addr, u32)> {
-        self.ports.mappings.get(&exposed_port)
+    pub fn host_port(&self, exposed_port: u32) -> Option<(Ipv4Addr, u32)> {
+        self.inspected().ports.get(exposed_port)
     }
 
     /// Same as `host_port`, but panics if the mapping could not be found.
-    pub fn host_port_unchecked(&self, exposed_port: u32) -> &(Ipv4Addr, u32) {
-        self.ports.mappings.get(&exposed_port).unwrap()
+    pub fn host_port_unchecked(&self, exposed_port: u32) -> (Ipv4Addr, u32) {
+        self.host_port(exposed_port).unwrap()
+    }
+
+    /// Inspect the current state of this container on the docker daemon.
+    pub async fn state(&self) -> Result<ContainerState, DockerTestError> {
+        state::inspect_state(&self.client, &self.id).await
+    }
+
+    /// Inspect this container anew, refreshing the values returned by [RunningContainer::ip]
+    /// and [RunningContainer::host_port].
+    pub async fn refresh(&self) -> Result<(), DockerTestError> {
+        let network = self
+            .inspected
+            .read()
+            .expect("failed to take inspected lock")
+            .network
+            .clone();
+
+        match network {
+            Some(network) => self.inspect(&network).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Clone this container without sharing its inspected details with the clone.
+    ///
+    /// Static containers are shared between tests, where each test inspects the container on
+    /// its own network.
+    pub(crate) fn clone_unshared(&self) -> RunningContainer {
+        RunningContainer {
+            inspected: Arc::new(RwLock::new(self.inspected())),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn inspected(&self) -> Inspected {
+        self.inspected
+            .read()
+            .expect("failed to take inspected lock")
+            .clone()
+    }
+
+    /// Inspect the container on the given network, refreshing its ip and port mappings.
+    pub(crate) async fn inspect(&self, network_name: &str) -> Result<(), DockerTestError> {
+        // On Windows container IPs cannot be resolved from outside a container.
+        // So container IPs in the test body are useless and the only way to contact a
+        // container is through a port map and localhost.
+        // To avoid have users to have cfg!(windows) in their test bodies, we simply set all
+        // container ips to localhost
+        //
+        // TODO: Find another strategy to contact containers from the test body on Windows.
+        if cfg!(windows) {
+            let mut inspected = self
+                .inspected
+                .write()
+                .expect("failed to take inspected lock");
+            inspected.network = Some(network_name.to_string());
+            inspected.ip = std::net::Ipv4Addr::new(127, 0, 0, 1);
+            return Ok(());
+        }
+
+        let details = self
+            .client
+            .inspect_container(&self.id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to inspect container: {}", e)))?;
+        let network_settings = details.network_settings.unwrap_or_default();
+
+        // Get the ip address from the network
+        let ip = if let Some(inspected_network) = network_settings
+            .networks
+            .as_ref()
+            .and_then(|n| n.get(network_name))
+        {
+            let ip_address = inspected_network.ip_address.as_deref().unwrap_or_default();
+            event!(Level::DEBUG, "container ip from inspect: {}", ip_address);
+            ip_address
+                .parse::<std::net::Ipv4Addr>()
+                // Exited containers will not have an IP address
+                .unwrap_or_else(|e| {
+                    event!(Level::TRACE, "container ip address failed to parse: {}", e);
+                    std::net::Ipv4Addr::UNSPECIFIED
+                })
+        } else {
+            std::net::Ipv4Addr::UNSPECIFIED
+        };
+
+        let ports = if let Some(ports) = network_settings.ports {
+            event!(
+                Level::DEBUG,
+                "container ports from inspect: {:?}",
+                ports.clone()
+            );
+            HostPortMappings::try_from(ports)
+                .map_err(|e| DockerTestError::HostPort(e.to_string()))?
+        } else {
+            HostPortMappings::default()
+        };
+
+        let mut inspected = self
+            .inspected
+            .write()
+            .expect("failed to take inspected lock");
+        *inspected = Inspected {
+            network: Some(network_name.to_string()),
+            ip,
+            ports,
+        };
+
+        Ok(())
     }
 
     /// Inspect the output of this container and await the presence of a log line.
@@ -156,18 +304,131 @@ impl RunningContainer {
     where
         T: Into<String> + Serialize,
     {
-        if let Err(e) = wait_for_message(
+        if let Err(e) = self
+            .wait_for_message(LogMatcher::substring(message), source, timeout)
+            .await
+        {
+            panic!("{}", e)
+        }
+    }
+
+    /// Inspect the output of this container and await log lines satisfying the matcher,
+    /// returning the matching lines.
+    ///
+    /// This is the non-panicking version of [RunningContainer::assert_message], which also
+    /// accepts a [LogMatcher] to await regular expressions or multiple occurrences.
+    pub async fn wait_for_message<M: Into<LogMatcher>>(
+        &self,
+        matcher: M,
+        source: MessageSource,
+        timeout: u16,
+    ) -> Result<Vec<String>, DockerTestError> {
+        waitfor::wait_for_message(
             &self.client,
             &self.id,
             &self.handle,
             source,
-            message,
+            &matcher.into(),
             timeout,
+            None,
         )
         .await
-        {
-            panic!("{}", e)
-        }
+    }
+
+    /// Stream the log output of this container line by line, as configured by the given
+    /// [LogStreamOptions].
+    ///
+    /// Lines are reassembled across the chunks received from the docker daemon, such that
+    /// each item is a complete line of either stdout or stderr.
+    pub fn logs(
+        &self,
+        options: LogStreamOptions,
+    ) -> BoxStream<'static, Result<LogLine, DockerTestError>> {
+        logs::logs(&self.client, &self.id, &options)
+    }
+
+    /// Retrieve all log output emitted by this container so far.
+    pub async fn logs_snapshot(&self) -> Result<Vec<LogLine>, DockerTestError> {
+        logs::snapshot(&self.client, &self.id).await
+    }
+
+    /// Retrieve a snapshot of the resource usage of this container.
+    ///
+    /// The daemon samples the container twice to compute the CPU usage, which takes
+    /// roughly a second.
+    pub async fn stats(&self) -> Result<ContainerStats, DockerTestError> {
+        stats::snapshot(&self.client, &self.id).await
+    }
+
+    /// Stream snapshots of the resource usage of this container, sampled by the daemon
+    /// roughly every second until the container exits or the stream is dropped.
+    pub fn stats_stream(&self) -> BoxStream<'static, Result<ContainerStats, DockerTestError>> {
+        stats::stream(&self.client, &self.id)
+    }
+
+    /// Execute the given command within this container, capturing its output and exit code.
+    ///
+    /// The first element of `cmd` is the executable and the remaining elements its arguments.
+    /// Use [RunningContainer::exec_with] to further configure the execution.
+    pub async fn exec<T: Into<String>>(&self, cmd: Vec<T>) -> Result<ExecOutput, DockerTestError> {
+        self.exec_with(ExecOptions::new(cmd)).await
+    }
+
+    /// Execute a command within this container, as configured by the given [ExecOptions].
+    ///
+    /// A command exiting with a non-zero exit code is not considered an error, inspect
+    /// [ExecOutput::exit_code] to determine whether the command succeeded.
+    pub async fn exec_with(&self, options: ExecOptions) -> Result<ExecOutput, DockerTestError> {
+        exec(&self.client, &self.handle, &self.id, options).await
+    }
+
+    /// Copy the host file or directory at `source` to the absolute `destination` path within
+    /// this container.
+    ///
+    /// The parent directory of `destination` must exist within the container. A directory is
+    /// copied recursively, such that `destination` mirrors its contents.
+    pub async fn copy_to<P: AsRef<Path>>(
+        &self,
+        source: P,
+        destination: &str,
+    ) -> Result<(), DockerTestError> {
+        copy::copy_to(
+            &self.client,
+            &self.id,
+            source.as_ref().to_path_buf(),
+            destination,
+        )
+        .await
+    }
+
+    /// Write `contents` to the file at the absolute `destination` path within this container,
+    /// replacing any existing file.
+    pub async fn write_file<T: AsRef<[u8]>>(
+        &self,
+        destination: &str,
+        contents: T,
+    ) -> Result<(), DockerTestError> {
+        copy::write_file(&self.client, &self.id, destination, contents.as_ref()).await
+    }
+
+    /// Retrieve a tar archive of the file or directory at the `source` path within this
+    /// container.
+    pub async fn copy_from(&self, source: &str) -> Result<Vec<u8>, DockerTestError> {
+        copy::copy_from(&self.client, &self.id, source).await
+    }
+
+    /// Read the contents of the regular file at the `source` path within this container.
+    pub async fn read_file(&self, source: &str) -> Result<Vec<u8>, DockerTestError> {
+        copy::read_file(&self.client, &self.id, source).await
+    }
+
+    /// Read the contents of the regular file at the `source` path within this container as a
+    /// string, failing if the file is not valid UTF-8.
+    pub async fn read_file_to_string(&self, source: &str) -> Result<String, DockerTestError> {
+        let contents = self.read_file(source).await?;
+        String::from_utf8(contents).map_err(|e| {
+            DockerTestError::Processing(format!("file `{}` is not valid UTF-8: {}", source, e))
+        })
     }
 }
 
@@ -178,10 +439,10 @@ impl From<PendingContainer> for RunningContainer {
             handle: container.handle,
             id: container.id,
             name: container.name,
-            ip: std::net::Ipv4Addr::UNSPECIFIED,
-            ports: HostPortMappings::default(),
+            inspected: Arc::new(RwLock::new(Inspected::default())),
             is_static: container.is_static,
             log_options: container.log_options,
+            wait: None,
         }
     }
 }
@@ -0,0 +1,245 @@
+//! Inspect the current state of a container.
+
+use crate::{DockerTestError, LogLine};
+
+use bollard::{
+    container::{InspectContainerOptions, WaitContainerOptions},
+    errors::Error,
+    models::{ContainerInspectResponse, ContainerStateStatusEnum, HealthStatusEnum},
+    Docker,
+};
+
+use futures::StreamExt;
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A snapshot of the state of a [RunningContainer], as reported by the docker daemon.
+///
+/// [RunningContainer]: crate::RunningContainer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerState {
+    /// The status of the container.
+    pub status: ContainerStatus,
+    /// The exit code of the container, `None` unless the container has exited.
+    pub exit_code: Option<i64>,
+    /// Whether the container was killed due to running out of memory.
+    pub oom_killed: bool,
+    /// The number of times the container has been restarted by the docker daemon.
+    pub restart_count: i64,
+    /// The health of the container, `None` if the container has no healthcheck.
+    pub health: Option<HealthStatus>,
+    /// When the container was last started.
+    pub started_at: Option<SystemTime>,
+    /// When the container last exited.
+    pub finished_at: Option<SystemTime>,
+}
+
+/// How a container exited, along with all of its log output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExitStatus {
+    /// The exit code of the container.
+    pub code: i64,
+    /// All log output emitted by the container.
+    pub logs: Vec<LogLine>,
+}
+
+impl ExitStatus {
+    /// Returns whether the container exited with exit code 0.
+    pub fn success(&self) -> bool {
+        self.code == 0
+    }
+}
+
+/// The status of a container.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerStatus {
+    /// The container has been created, but never started.
+    Created,
+    /// The container is running.
+    Running,
+    /// The container is paused.
+    Paused,
+    /// The container is being restarted by the docker daemon.
+    Restarting,
+    /// The container is being removed.
+    Removing,
+    /// The container has exited.
+    Exited,
+    /// The container could not be removed and is defunct.
+    Dead,
+    /// The docker daemon did not report a known status.
+    Unknown,
+}
+
+/// The health of a container with a healthcheck.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HealthStatus {
+    /// The container is running its initial healthchecks.
+    Starting,
+    /// The healthcheck of the container succeeds.
+    Healthy,
+    /// The healthcheck of the container failed the configured number of consecutive times.
+    Unhealthy,
+}
+
+impl From<Option<ContainerStateStatusEnum>> for ContainerStatus {
+    fn from(status: Option<ContainerStateStatusEnum>) -> ContainerStatus {
+        match status {
+            Some(ContainerStateStatusEnum::CREATED) => ContainerStatus::Created,
+            Some(ContainerStateStatusEnum::RUNNING) => ContainerStatus::Running,
+            Some(ContainerStateStatusEnum::PAUSED) => ContainerStatus::Paused,
+            Some(ContainerStateStatusEnum::RESTARTING) => ContainerStatus::Restarting,
+            Some(ContainerStateStatusEnum::REMOVING) => ContainerStatus::Removing,
+            Some(ContainerStateStatusEnum::EXITED) => ContainerStatus::Exited,
+            Some(ContainerStateStatusEnum::DEAD) => ContainerStatus::Dead,
+            Some(ContainerStateStatusEnum::EMPTY) | None => ContainerStatus::Unknown,
+        }
+    }
+}
+
+impl HealthStatus {
+    pub(crate) fn from_inspect(status: Option<HealthStatusEnum>) -> Option<HealthStatus> {
+        match status? {
+            HealthStatusEnum::STARTING => Some(HealthStatus::Starting),
+            HealthStatusEnum::HEALTHY => Some(HealthStatus::Healthy),
+            HealthStatusEnum::UNHEALTHY => Some(HealthStatus::Unhealthy),
+            HealthStatusEnum::EMPTY | HealthStatusEnum::NONE => None,
+        }
+    }
+}
+
+/// Parse an RFC 3339 timestamp in UTC as reported by the docker daemon, e.g.,
+/// `2023-08-01T12:34:56.123456789Z`.
+///
+/// The daemon reports the zero time `0001-01-01T00:00:00Z` for events that never occurred,
+/// which, along with unparsable timestamps, yields `None`.
+pub(crate) fn parse_timestamp(timestamp: &str) -> Option<SystemTime> {
+    let timestamp = timestamp.strip_suffix('Z')?;
+    let (date, time) = timestamp.split_once('T')?;
+
+    let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+    let mut time = time.splitn(3, ':').map(|p| p.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        let digits = &fraction[..fraction.len().min(9)];
+        digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32)
+    };
+
+    // Days since the unix epoch of the civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let secs = u64::try_from(days).ok()? * 86400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::new(secs, nanos))
+}
+
+/// Inspect the current state of the container `id`.
+pub(crate) async fn inspect_state(
+    client: &Docker,
+    id: &str,
+) -> Result<ContainerState, DockerTestError> {
+    let details = client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| DockerTestError::Daemon(format!("failed to inspect container: {}", e)))?;
+    let state = details.state.unwrap_or_default();
+    let status = ContainerStatus::from(state.status);
+
+    Ok(ContainerState {
+        status,
+        exit_code: state
+            .exit_code
+            .filter(|_| matches!(status, ContainerStatus::Exited | ContainerStatus::Dead)),
+        oom_killed: state.oom_killed.unwrap_or_default(),
+        restart_count: details.restart_count.unwrap_or_default(),
+        health: HealthStatus::from_inspect(state.health.and_then(|h| h.status)),
+        started_at: state.started_at.as_deref().and_then(parse_timestamp),
+        finished_at: state.finished_at.as_deref().and_then(parse_timestamp),
+    })
+}
+
+/// Unpause the container `id` if it is paused, as paused containers can neither be stopped
+/// nor removed.
+///
+/// The container may be unpaused concurrently, e.g., by a dropped [RunningContainer::pause_for],
+/// which is not considered a failure.
+///
+/// [RunningContainer::pause_for]: crate::RunningContainer::pause_for
+pub(crate) async fn unpause_if_paused(client: &Docker, id: &str) -> Result<(), Error> {
+    let is_paused = |details: &ContainerInspectResponse| {
+        let status = details.state.as_ref().and_then(|s| s.status.as_ref());
+        status == Some(&ContainerStateStatusEnum::PAUSED)
+    };
+
+    let details = client
+        .inspect_container(id, None::<InspectContainerOptions>)
+        .await?;
+    if !is_paused(&details) {
+        return Ok(());
+    }
+
+    match client.unpause_container(id).await {
+        Ok(()) => Ok(()),
+        Err(e) => match client
+            .inspect_container(id, None::<InspectContainerOptions>)
+            .await
+        {
+            Ok(details) if !is_paused(&details) => Ok(()),
+            _ => Err(e),
+        },
+    }
+}
+
+/// Await the container `id` no longer running, returning its exit code.
+pub(crate) async fn wait_exit_code(client: &Docker, id: &str) -> Result<i64, DockerTestError> {
+    let options = WaitContainerOptions {
+        condition: "not-running",
+    };
+
+    // The client reports non-zero exit codes as errors.
+    match client.wait_container(id, Some(options)).next().await {
+        Some(Ok(response)) => Ok(response.status_code),
+        Some(Err(Error::DockerContainerWaitError { error, code })) if error.is_empty() => Ok(code),
+        Some(Err(e)) => Err(DockerTestError::Daemon(format!(
+            "failed to wait for container to exit: {}",
+            e
+        ))),
+        None => Err(DockerTestError::Daemon(
+            "daemon did not report the exit of the container".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::state::parse_timestamp;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00Z"), Some(UNIX_EPOCH),);
+        assert_eq!(
+            parse_timestamp("2023-08-01T12:34:56.123456789Z"),
+            Some(UNIX_EPOCH + Duration::new(1690893296, 123456789))
+        );
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00.5Z"),
+            Some(UNIX_EPOCH + Duration::new(1709164800, 500_000_000))
+        );
+        assert_eq!(parse_timestamp("0001-01-01T00:00:00Z"), None);
+        assert_eq!(parse_timestamp("not a timestamp"), None);
+    }
+}
@@ -0,0 +1,190 @@
+//! Resource usage statistics of a running container.
+
+use crate::DockerTestError;
+
+use bollard::{
+    container::{BlkioStatsEntry, MemoryStats, MemoryStatsStats, Stats, StatsOptions},
+    Docker,
+};
+use futures::stream::{BoxStream, StreamExt};
+
+/// A snapshot of the resource usage of a [RunningContainer].
+///
+/// [RunningContainer]: crate::RunningContainer
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContainerStats {
+    /// The CPU usage since the previous sample, where 100% equals one fully utilized CPU core.
+    pub cpu_percent: f64,
+    /// The memory usage in bytes, excluding the inactive page cache.
+    pub memory_usage: u64,
+    /// The memory limit in bytes.
+    pub memory_limit: u64,
+    /// The number of bytes received over all network interfaces.
+    pub network_rx_bytes: u64,
+    /// The number of bytes transmitted over all network interfaces.
+    pub network_tx_bytes: u64,
+    /// The number of bytes read from block devices.
+    pub block_read_bytes: u64,
+    /// The number of bytes written to block devices.
+    pub block_write_bytes: u64,
+    /// The number of processes and threads.
+    pub pids: u64,
+}
+
+impl From<Stats> for ContainerStats {
+    fn from(stats: Stats) -> ContainerStats {
+        let cpus = stats.cpu_stats.online_cpus.unwrap_or_else(|| {
+            stats
+                .cpu_stats
+                .cpu_usage
+                .percpu_usage
+                .as_ref()
+                .map(|p| p.len() as u64)
+                .unwrap_or(1)
+        });
+        let cpu_percent = cpu_percent(
+            (
+                stats.cpu_stats.cpu_usage.total_usage,
+                stats.precpu_stats.cpu_usage.total_usage,
+            ),
+            (
+                stats.cpu_stats.system_cpu_usage,
+                stats.precpu_stats.system_cpu_usage,
+            ),
+            cpus,
+        );
+
+        // Older daemons only report a single network interface.
+        let (network_rx_bytes, network_tx_bytes) = match (&stats.networks, &stats.network) {
+            (Some(networks), _) => networks.values().collect(),
+            (None, network) => network.iter().collect::<Vec<_>>(),
+        }
+        .into_iter()
+        .fold((0, 0), |(rx, tx), n| (rx + n.rx_bytes, tx + n.tx_bytes));
+
+        let (block_read_bytes, block_write_bytes) = block_io(
+            stats
+                .blkio_stats
+                .io_service_bytes_recursive
+                .as_deref()
+                .unwrap_or_default(),
+        );
+
+        ContainerStats {
+            cpu_percent,
+            memory_usage: memory_usage(&stats.memory_stats),
+            memory_limit: stats.memory_stats.limit.unwrap_or_default(),
+            network_rx_bytes,
+            network_tx_bytes,
+            block_read_bytes,
+            block_write_bytes,
+            pids: stats.pids_stats.current.unwrap_or_default(),
+        }
+    }
+}
+
+/// Compute the CPU usage percentage between two samples, as reported by `docker stats`.
+fn cpu_percent(total: (u64, u64), system: (Option<u64>, Option<u64>), cpus: u64) -> f64 {
+    let cpu_delta = total.0.saturating_sub(total.1) as f64;
+    let system_delta = match system {
+        (Some(current), Some(previous)) => current.saturating_sub(previous) as f64,
+        _ => return 0.0,
+    };
+
+    if cpu_delta > 0.0 && system_delta > 0.0 {
+        cpu_delta / system_delta * cpus as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// The memory usage excluding the inactive page cache, as reported by `docker stats`.
+fn memory_usage(memory: &MemoryStats) -> u64 {
+    let usage = memory.usage.unwrap_or_default();
+    let cache = match memory.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+    usage.saturating_sub(cache)
+}
+
+/// Sum the bytes read and written across all block devices.
+fn block_io(entries: &[BlkioStatsEntry]) -> (u64, u64) {
+    entries.iter().fold((0, 0), |(read, write), e| {
+        match e.op.to_ascii_lowercase().as_str() {
+            "read" => (read + e.value, write),
+            "write" => (read, write + e.value),
+            _ => (read, write),
+        }
+    })
+}
+
+fn stats(
+    client: &Docker,
+    id: &str,
+    stream: bool,
+) -> BoxStream<'static, Result<ContainerStats, DockerTestError>> {
+    // A one-shot sample lacks the previous CPU sample, we therefore let the daemon take two.
+    let options = StatsOptions {
+        stream,
+        one_shot: false,
+    };
+
+    client
+        .stats(id, Some(options))
+        .map(|s| {
+            s.map(ContainerStats::from).map_err(|e| {
+                DockerTestError::Daemon(format!("failed to retrieve container stats: {}", e))
+            })
+        })
+        .boxed()
+}
+
+/// Retrieve a single resource usage sample of the container `id`.
+pub(crate) async fn snapshot(client: &Docker, id: &str) -> Result<ContainerStats, DockerTestError> {
+    stats(client, id, false).next().await.unwrap_or_else(|| {
+        Err(DockerTestError::Daemon(
+            "daemon returned no container stats".to_string(),
+        ))
+    })
+}
+
+/// Stream resource usage samples of the container `id`, as emitted by the daemon.
+pub(crate) fn stream(
+    client: &Docker,
+    id: &str,
+) -> BoxStream<'static, Result<ContainerStats, DockerTestError>> {
+    stats(client, id, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::stats::{block_io, cpu_percent};
+
+    use bollard::container::BlkioStatsEntry;
+
+    #[test]
+    fn test_cpu_percent() {
+        assert_eq!(cpu_percent((300, 100), (Some(2000), Some(1000)), 2), 40.0);
+        assert_eq!(cpu_percent((300, 100), (Some(2000), None), 2), 0.0);
+        assert_eq!(cpu_percent((100, 100), (Some(2000), Some(1000)), 2), 0.0);
+    }
+
+    #[test]
+    fn test_block_io() {
+        let entry = |op: &str, value| BlkioStatsEntry {
+            major: 8,
+            minor: 0,
+            op: op.to_string(),
+            value,
+        };
+        let entries = vec![
+            entry("Read", 10),
+            entry("write", 20),
+            entry("Total", 30),
+            entry("read", 5),
+        ];
+        assert_eq!(block_io(&entries), (15, 20));
+    }
+}
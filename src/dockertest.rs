@@ -2,11 +2,15 @@
 
 use crate::composition::Composition;
 use crate::image::Source;
+use crate::plan::{self, Plan};
 use crate::runner::{DockerOperations, Runner};
 use crate::specification::ContainerSpecification;
+use crate::validation;
 use crate::DockerTestError;
 
 use futures::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
 use tokio::runtime::Runtime;
 use tracing::{event, span, Instrument, Level};
 
@@ -29,6 +33,13 @@ pub struct DockerTest {
     /// Network configuration, defaults to [Network::Singular] if not specified by
     /// user.
     pub(crate) network: Network,
+    /// The deadline for all containers to become ready, measured from when the first
+    /// container is started.
+    pub(crate) startup_timeout: Option<Duration>,
+    /// The deadline for the test body to complete.
+    pub(crate) test_timeout: Option<Duration>,
+    /// Where to write the run report, if any.
+    pub(crate) report_path: Option<PathBuf>,
 }
 
 /// Configure how the docker network should be applied to the containers within this test.
@@ -67,6 +78,9 @@ impl DockerTest {
             namespace: "dockertest-rs".to_string(),
             container_id: None,
             network: Network::Singular,
+            startup_timeout: None,
+            test_timeout: None,
+            report_path: None,
         }
     }
 
@@ -99,6 +113,51 @@ impl DockerTest {
         Self { network, ..self }
     }
 
+    /// Sets the deadline for all containers to become ready.
+    ///
+    /// Once the deadline expires, the [WaitFor] of every container that is still starting is
+    /// cancelled, all containers are torn down, and the test fails with a startup error for each
+    /// container that was not ready. By default, there is no deadline.
+    ///
+    /// A deadline for a single container is configured through `set_startup_timeout` on its
+    /// container specification.
+    ///
+    /// [WaitFor]: crate::waitfor::WaitFor
+    pub fn with_startup_timeout(self, timeout: Duration) -> Self {
+        Self {
+            startup_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the deadline for the test body to complete.
+    ///
+    /// Once the deadline expires, the test body is aborted, container logs are handled as if
+    /// the test failed, and all containers are torn down before the test fails with a timeout
+    /// error. By default, there is no deadline.
+    pub fn with_test_timeout(self, timeout: Duration) -> Self {
+        Self {
+            test_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Write a JSON report of the test run to the given path once the test completes.
+    ///
+    /// The report contains the outcome of the test, the timestamp of each phase of the run,
+    /// and per container its handle, name, id, image id, start policy and lifecycle
+    /// timestamps, alongside any teardown errors.
+    ///
+    /// Alternatively, the `DOCKERTEST_REPORT_DIR` environment variable enables a report for
+    /// every test, each written to its own file within that directory. This path takes
+    /// precedence over the environment variable.
+    pub fn with_report_path<T: Into<PathBuf>>(self, path: T) -> Self {
+        Self {
+            report_path: Some(path.into()),
+            ..self
+        }
+    }
+
     /// Append a container specification as part of this specific test.
     ///
     /// The order of which container specifications are added to DockerTest is significant
@@ -121,6 +180,36 @@ impl DockerTest {
         self
     }
 
+    /// Validate the configuration of this test, prior to interacting with the docker daemon.
+    ///
+    /// Every problem is reported at once through [DockerTestError::Validation], such as
+    /// duplicate handles, references to unknown handles, host ports bound multiple times,
+    /// invalid container names and external containers without a name.
+    ///
+    /// This validation is always performed when the test is run.
+    pub fn validate(&self) -> Result<(), DockerTestError> {
+        let errors = validation::validate(self);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DockerTestError::Validation(errors))
+        }
+    }
+
+    /// Describe the environment this test would create, without interacting with the docker
+    /// daemon.
+    ///
+    /// The [Plan] contains the final container names, named volumes, network name,
+    /// injected container name environment variables, port maps and how each image would be
+    /// retrieved. Handle collisions, unknown handles and dependency cycles are detected the same
+    /// way as when the test is run.
+    ///
+    /// The random suffixes of container names, volume names and isolated networks differ
+    /// between the plan and the actual run.
+    pub fn plan(&self) -> Result<Plan, DockerTestError> {
+        plan::resolve(self)
+    }
+
     /// Retrieve the default source for Images unless explicitly specified per Image.
     pub fn source(&self) -> &Source {
         &self.default_source
@@ -149,6 +238,10 @@ impl DockerTest {
             }
         };
 
+        if let Err(e) = self.validate() {
+            process_run(Err(e));
+        }
+
         let runner = rt.block_on(Runner::new(self));
         process_run(rt.block_on(runner.run_impl(test).in_current_span()))
     }
@@ -167,6 +260,10 @@ impl DockerTest {
         let span = span!(Level::ERROR, "run");
         let _guard = span.enter();
 
+        if let Err(e) = self.validate() {
+            process_run(Err(e));
+        }
+
         let runner = Runner::new(self).await;
         process_run(runner.run_impl(test).in_current_span().await);
     }
@@ -239,4 +336,42 @@ mod tests {
 
         assert!(equal, "default_source was not set correctly");
     }
+
+    // The `with_startup_timeout` builder method sets the startup_timeout correctly
+    #[test]
+    fn test_with_startup_timeout() {
+        let timeout = std::time::Duration::from_secs(10);
+        let test = DockerTest::new().with_startup_timeout(timeout);
+
+        assert_eq!(
+            test.startup_timeout,
+            Some(timeout),
+            "startup_timeout was not set correctly"
+        );
+    }
+
+    // The `with_test_timeout` builder method sets the test_timeout correctly
+    #[test]
+    fn test_with_test_timeout() {
+        let timeout = std::time::Duration::from_secs(10);
+        let test = DockerTest::new().with_test_timeout(timeout);
+
+        assert_eq!(
+            test.test_timeout,
+            Some(timeout),
+            "test_timeout was not set correctly"
+        );
+    }
+
+    // The `with_report_path` builder method sets the report_path correctly
+    #[test]
+    fn test_with_report_path() {
+        let test = DockerTest::new().with_report_path("report.json");
+
+        assert_eq!(
+            test.report_path,
+            Some(std::path::PathBuf::from("report.json")),
+            "report_path was not set correctly"
+        );
+    }
 }
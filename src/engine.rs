@@ -1,24 +1,28 @@
 //! The meaty internals of executing a single test.
 
-use crate::composition::{Composition, LogPolicy};
+use crate::composition::{Composition, LogPolicy, OnReady};
 use crate::container::{
-    CleanupContainer, CreatedContainer, HostPortMappings, PendingContainer, RunningContainer,
-    StaticExternalContainer,
+    unpause_if_paused, CleanupContainer, CreatedContainer, PendingContainer, RunningContainer,
+    StartOutcome, StaticExternalContainer,
 };
+use crate::error::{ContainerFailure, LifecyclePhase};
+use crate::plan::ContainerPlan;
+use crate::report::{ContainerReport, Transition};
 use crate::static_container::STATIC_CONTAINERS;
 use crate::utils::generate_random_string;
 use crate::{DockerTestError, Network, Source, StartPolicy};
 
 use bollard::{
-    container::{InspectContainerOptions, RemoveContainerOptions, StopContainerOptions},
+    container::{RemoveContainerOptions, StopContainerOptions},
     Docker,
 };
-use futures::future::join_all;
-use tokio::task::JoinHandle;
+use futures::future::{join_all, Future, FutureExt};
+use futures::stream::{FuturesUnordered, StreamExt};
 use tracing::{event, Level};
 
 use std::collections::{hash_map::Entry, HashMap, HashSet};
-use std::convert::TryFrom;
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
 
 /// The initial phase.
 pub struct Bootstrapping {
@@ -49,9 +53,8 @@ pub struct Debris {
 enum Transitional {
     Pending(PendingContainer),
     Running(RunningContainer),
-    CreationFailure(DockerTestError),
+    CreationFailure(ContainerFailure),
     StaticExternal(StaticExternalContainer),
-    Sentinel,
 }
 
 /// The purpose of the Keeper is to hold the reference to each Container throughout the test,
@@ -66,6 +69,14 @@ struct Keeper {
     lookup_collisions: HashSet<String>,
     /// This map stores the mapping between a handle and its index into `kept`.
     lookup_handlers: HashMap<String, usize>,
+    /// The resolved dependency graph, indexed the same way as `kept`.
+    /// Each entry holds the indices into `kept` that must be ready before the container
+    /// can be started.
+    dependencies: Vec<Vec<usize>>,
+    /// The `on_ready` hook of each container, indexed the same way as `kept`.
+    on_ready: Vec<Option<OnReady>>,
+    /// The lifecycle report of each container, indexed the same way as `kept`.
+    reports: Vec<ContainerReport>,
 }
 
 // NOTE: Clone is only derived for Engine<Orbiting>, to delegate ownership into DockerOperations.
@@ -97,6 +108,19 @@ pub(crate) fn bootstrap(compositions: Vec<Composition>) -> Engine<Bootstrapping>
     let keeper = Keeper {
         lookup_collisions: collisions,
         lookup_handlers: handlers,
+        dependencies: vec![Vec::new(); compositions.len()],
+        on_ready: compositions.iter().map(|c| c.on_ready.clone()).collect(),
+        reports: compositions
+            .iter()
+            .map(|c| ContainerReport {
+                handle: c.handle(),
+                name: c.container_name.clone(),
+                id: None,
+                image_id: None,
+                start_policy: c.start_policy.clone(),
+                transitions: Vec::new(),
+            })
+            .collect(),
     };
 
     Engine {
@@ -108,9 +132,15 @@ pub(crate) fn bootstrap(compositions: Vec<Composition>) -> Engine<Bootstrapping>
 impl Engine<Bootstrapping> {
     /// Perform the magic transformation info the final container name.
     pub fn resolve_final_container_name(&mut self, namespace: &str) {
-        for c in self.phase.kept.iter_mut() {
+        for (c, report) in self
+            .phase
+            .kept
+            .iter_mut()
+            .zip(self.keeper.reports.iter_mut())
+        {
             let suffix = generate_random_string(20);
             c.configure_container_name(namespace, &suffix);
+            report.name = c.container_name.clone();
         }
     }
 
@@ -165,12 +195,90 @@ impl Engine<Fueling> {
         Ok(())
     }
 
+    /// Retrieve every handle that is used by multiple compositions.
+    pub fn handle_collisions(&self) -> Vec<String> {
+        let mut collisions: Vec<String> = self.keeper.lookup_collisions.iter().cloned().collect();
+        collisions.sort();
+        collisions
+    }
+
+    /// Describe each composition as it would be created.
+    pub fn plan_containers(&self, default_source: &Source) -> Vec<ContainerPlan> {
+        self.phase
+            .kept
+            .iter()
+            .map(|c| c.plan(default_source))
+            .collect()
+    }
+
+    /// Resolve the dependency graph between all `Composition`s.
+    ///
+    /// Explicitly declared dependencies are combined with the implicit ordering of
+    /// [StartPolicy::Strict] compositions, where each strict composition depends on the
+    /// previously added strict composition. The resulting graph must be acyclic.
+    pub fn resolve_dependencies(&mut self) -> Result<(), DockerTestError> {
+        let mut dependencies: Vec<Vec<usize>> = Vec::with_capacity(self.phase.kept.len());
+        let mut previous_strict: Option<usize> = None;
+
+        for (i, c) in self.phase.kept.iter().enumerate() {
+            let mut edges = Vec::new();
+
+            for handle in c.dependencies.iter() {
+                // Guard against duplicate handle usage.
+                if self.keeper.lookup_collisions.contains(handle) {
+                    return Err(DockerTestError::Startup(format!(
+                        "composition `{}` attempted to depend on duplicate handle `{}`",
+                        c.handle(),
+                        handle
+                    )));
+                }
+
+                match self.keeper.lookup_handlers.get(handle) {
+                    Some(index) => {
+                        if !edges.contains(index) {
+                            edges.push(*index);
+                        }
+                    }
+                    None => {
+                        return Err(DockerTestError::Startup(format!(
+                            "composition `{}` attempted to depend on non-existent handle `{}`",
+                            c.handle(),
+                            handle
+                        )))
+                    }
+                }
+            }
+
+            if c.start_policy == StartPolicy::Strict {
+                if let Some(previous) = previous_strict {
+                    if !edges.contains(&previous) {
+                        edges.push(previous);
+                    }
+                }
+                previous_strict = Some(i);
+            }
+
+            dependencies.push(edges);
+        }
+
+        if let Some(cycle) = find_cycle(&dependencies) {
+            let cycle = cycle
+                .into_iter()
+                .map(|i| self.phase.kept[i].handle())
+                .collect();
+            return Err(DockerTestError::DependencyCycle { cycle });
+        }
+
+        self.keeper.dependencies = dependencies;
+        Ok(())
+    }
+
     /// Pull the `Image` of all `Composition`s.
     ///
     /// This will ensure that all docker images is present on the local daemon
     /// and we are able to issue a create container operation.
     pub async fn pull_images(
-        &self,
+        &mut self,
         client: &Docker,
         default: &Source,
     ) -> Result<(), DockerTestError> {
@@ -178,12 +286,23 @@ impl Engine<Fueling> {
 
         // QUESTION: Can we not iter().map() this?
         for composition in self.phase.kept.iter() {
-            let fut = composition.image().pull(client, default);
+            let fut = composition
+                .image()
+                .pull(client, default)
+                .map(|result| (result, SystemTime::now()));
 
             future_vec.push(fut);
         }
 
-        join_all(future_vec).await;
+        let pulled = join_all(future_vec).await;
+        for ((result, time), report) in pulled.into_iter().zip(self.keeper.reports.iter_mut()) {
+            let phase = if result.is_ok() {
+                "pulled"
+            } else {
+                "pull_failed"
+            };
+            report.transitions.push(Transition::at(phase, time));
+        }
         Ok(())
     }
 
@@ -199,31 +318,54 @@ impl Engine<Fueling> {
         // NOTE: The insertion order is preserved.
         // To achieve this, we need to keep all inserted compositions when they also represent
         // a static external container.
-        let created: Vec<Result<CreatedContainer, DockerTestError>> = join_all(
-            self.phase
-                .kept
-                .into_iter()
-                .map(|c| c.create(client, Some(network), network_settings)),
-        )
-        .await;
+        let mut keeper = self.keeper;
+        let handles: Vec<String> = self.phase.kept.iter().map(|c| c.handle()).collect();
+        for (c, report) in self.phase.kept.iter().zip(keeper.reports.iter_mut()) {
+            report.image_id = Some(c.image().retrieved_id());
+        }
+
+        let created: Vec<(Result<CreatedContainer, DockerTestError>, SystemTime)> =
+            join_all(self.phase.kept.into_iter().map(|c| {
+                c.create(client, Some(network), network_settings)
+                    .map(|result| (result, SystemTime::now()))
+            }))
+            .await;
 
         let mut startup_failure = false;
         let kept = created
             .into_iter()
-            .map(|c| match c {
-                Ok(c) => match c {
-                    CreatedContainer::StaticExternal(e) => Transitional::StaticExternal(e),
-                    CreatedContainer::Pending(p) => Transitional::Pending(p),
-                },
+            .zip(handles)
+            .zip(keeper.reports.iter_mut())
+            .map(|(((c, time), handle), report)| match c {
+                Ok(c) => {
+                    report.transitions.push(Transition::at("created", time));
+                    match c {
+                        CreatedContainer::StaticExternal(e) => {
+                            report.id = Some(e.id.clone());
+                            Transitional::StaticExternal(e)
+                        }
+                        CreatedContainer::Pending(p) => {
+                            report.id = Some(p.id.clone());
+                            Transitional::Pending(p)
+                        }
+                    }
+                }
                 Err(e) => {
                     startup_failure = true;
-                    Transitional::CreationFailure(e)
+                    report
+                        .transitions
+                        .push(Transition::at("creation_failed", time));
+                    Transitional::CreationFailure(ContainerFailure::new(
+                        handle,
+                        LifecyclePhase::Creation,
+                        e,
+                    ))
                 }
             })
             .collect();
 
         let engine = Engine::<Igniting> {
-            keeper: self.keeper,
+            keeper,
             phase: Igniting { kept },
         };
         if startup_failure {
@@ -238,10 +380,16 @@ impl Engine<Igniting> {
     /// Move the engine forward into [Orbiting] phase.
     ///
     /// This will start and execute the relevant waitfor directives for each container.
+    /// On error, every container that failed to start is reported.
+    ///
+    /// The optional `startup_timeout` bounds the time it takes for all containers to
+    /// become ready.
     pub async fn orbiting(
         mut self,
-    ) -> Result<Engine<Orbiting>, (Engine<Igniting>, DockerTestError)> {
-        let result = self.start_containers().await;
+        startup_timeout: Option<Duration>,
+    ) -> Result<Engine<Orbiting>, (Engine<Igniting>, Vec<ContainerFailure>)> {
+        let deadline = startup_timeout.map(|t| (Instant::now() + t, t));
+        let result = self.start_containers(deadline).await;
 
         match result {
             Ok(_) => Ok(Engine::<Orbiting> {
@@ -254,154 +402,189 @@ impl Engine<Igniting> {
         }
     }
 
-    // TODO: Refactor to return Vec<DockerTestError> on Err
-    async fn start_containers(&mut self) -> Result<(), DockerTestError> {
-        // We clone out all our pending containers.
-        // This will simplify alot of the gathering logic. We may be able to avoid this
-        // clone in the future if we commit to changing the [WaitFor] signature.
-        //
-        // We manipulate the kept indices by correlating the ids to update with the running
-        // transformed container.
-        let pending = self.phase.kept.iter().flat_map(|t| match t {
-            Transitional::Pending(p) => Some(p.clone()),
-            _ => None,
-        });
+    async fn start_containers(
+        &mut self,
+        deadline: Option<(Instant, Duration)>,
+    ) -> Result<(), Vec<ContainerFailure>> {
+        let dependencies = &self.keeper.dependencies;
+
+        // The inverse of the dependency graph, used to locate which containers that might
+        // be ready to start once a container has been started.
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); dependencies.len()];
+        for (i, deps) in dependencies.iter().enumerate() {
+            for dep in deps.iter() {
+                dependents[*dep].push(i);
+            }
+        }
 
-        let (relaxed, strict): (Vec<_>, Vec<_>) = pending
-            .into_iter()
-            .partition(|c| c.start_policy == StartPolicy::Relaxed);
-
-        // Asynchronously start all relaxed containers.
-        let starting_relaxed = Self::start_relaxed_containers(relaxed);
-        let strict_success = Self::start_strict_containers(strict).await?;
-        let relaxed_success = Self::wait_for_relaxed_containers(starting_relaxed).await?;
-
-        let mut containers = Vec::new();
-        containers.extend(strict_success.into_iter());
-        containers.extend(relaxed_success.into_iter());
-        containers.extend(STATIC_CONTAINERS.external_containers().await.into_iter());
-
-        // An important consideration herein is to maintain the same insertion order
-        // of the original vector, when updating our Transitional::* variants.
-        // This is due to the [Keeper] holding the handle -> indices lookup table,
-        // which we must use to resolve the correct [RunningContainer]
-        for started in containers.into_iter() {
-            // Locate the entry into kept of the started container
-            let position = match self.phase.kept.iter().position(|x| match x {
-                Transitional::Pending(p) => p.id == started.id,
-                Transitional::StaticExternal(e) => e.handle == started.handle,
-                _ => false,
-            }) {
-                Some(e) => e,
-                None => continue,
-            };
+        // The number of dependencies we are still waiting on per container.
+        // Static external containers are never started by us, and are thus already ready.
+        let mut remaining: Vec<usize> = dependencies
+            .iter()
+            .map(|deps| {
+                deps.iter()
+                    .filter(|d| matches!(self.phase.kept[**d], Transitional::Pending(_)))
+                    .count()
+            })
+            .collect();
 
-            // Create the [RunningContainer] variant out of the pending
-            let current = std::mem::replace(&mut self.phase.kept[position], Transitional::Sentinel);
-            let running = match current {
-                Transitional::Pending(_) | Transitional::StaticExternal(_) => {
-                    Transitional::Running(started)
+        // We clone out the pending containers we start.
+        // We may be able to avoid this clone in the future if we commit to changing
+        // the [WaitFor] signature.
+        let mut starting = FuturesUnordered::new();
+        for (i, transitional) in self.phase.kept.iter().enumerate() {
+            if let Transitional::Pending(p) = transitional {
+                if remaining[i] == 0 {
+                    self.keeper.reports[i]
+                        .transitions
+                        .push(Transition::now("starting"));
+                    starting.push(Self::start_container(i, p.clone(), deadline));
                 }
-                _ => continue,
-            };
-
-            self.phase.kept[position] = running;
+            }
         }
 
-        Ok(())
-    }
-
-    // Implementation detail
-    fn start_relaxed_containers(
-        containers: Vec<PendingContainer>,
-    ) -> Vec<JoinHandle<Result<RunningContainer, DockerTestError>>> {
-        event!(Level::TRACE, "starting relaxed containers");
-        containers
-            .into_iter()
-            .map(|c| tokio::spawn(c.start()))
-            .collect()
-    }
-
-    // Implementation detail
-    // We currently only report the first error
-    async fn start_strict_containers(
-        pending: Vec<PendingContainer>,
-    ) -> Result<Vec<RunningContainer>, DockerTestError> {
-        let mut running = vec![];
-        let mut first_error = None;
-
-        event!(Level::TRACE, "beginning starting strict containers");
-        for c in pending.into_iter() {
-            match c.start().await {
-                Ok(r) => running.push(r),
-                Err(e) => {
-                    event!(Level::ERROR, "starting strict container failed {}", e);
-                    first_error = Some(e);
-                    break;
+        event!(Level::TRACE, "starting containers");
+
+        let mut failures = Vec::new();
+        while let Some((index, retries, result)) = starting.next().await {
+            self.keeper.reports[index].transitions.extend(
+                retries
+                    .into_iter()
+                    .map(|time| Transition::at("retrying", time)),
+            );
+            match result {
+                Ok(running) => {
+                    let report = &mut self.keeper.reports[index];
+                    report.id = Some(running.id.clone());
+                    report.transitions.push(Transition::now("started"));
+
+                    // The insertion order of `kept` is maintained, since the [Keeper] holds
+                    // the handle -> indices lookup table.
+                    self.phase.kept[index] = Transitional::Running(running);
+
+                    for dependent in dependents[index].iter() {
+                        remaining[*dependent] -= 1;
+                        if remaining[*dependent] != 0 {
+                            continue;
+                        }
+                        if let Transitional::Pending(p) = &self.phase.kept[*dependent] {
+                            self.keeper.reports[*dependent]
+                                .transitions
+                                .push(Transition::now("starting"));
+                            starting.push(Self::start_container(*dependent, p.clone(), deadline));
+                        }
+                    }
+                }
+                // Dependents of a failed container are never started.
+                Err((last_attempt, e)) => {
+                    event!(Level::ERROR, "starting container failed {}", e);
+                    let report = &mut self.keeper.reports[index];
+                    report.transitions.push(Transition::now("startup_failed"));
+                    let handle = match &self.phase.kept[index] {
+                        Transitional::Pending(p) => p.handle.clone(),
+                        _ => String::new(),
+                    };
+                    let failure = ContainerFailure::new(handle, LifecyclePhase::Startup, e);
+                    match last_attempt {
+                        Some(pending) => {
+                            report.id = Some(pending.id.clone());
+                            self.phase.kept[index] = Transitional::Pending(pending);
+                        }
+                        // Recreating the container failed, thus no container remains to be
+                        // torn down.
+                        None => {
+                            self.phase.kept[index] = Transitional::CreationFailure(failure.clone())
+                        }
+                    }
+                    failures.push(failure);
                 }
             }
         }
 
         event!(
             Level::TRACE,
-            "finished starting strict containers with result: {}",
-            first_error.is_none()
+            "finished starting containers with result: {}",
+            failures.is_empty()
         );
 
-        match first_error {
-            None => Ok(running),
-            Some(e) => Err(e),
-        }
-    }
-
-    // Implementation detail
-    async fn wait_for_relaxed_containers(
-        starting_relaxed: Vec<JoinHandle<Result<RunningContainer, DockerTestError>>>,
-    ) -> Result<Vec<RunningContainer>, DockerTestError> {
-        let mut running_relaxed: Vec<RunningContainer> = Vec::new();
-        let mut first_error = None;
-
-        for join_handle in join_all(starting_relaxed).await {
-            match join_handle {
-                Ok(start_result) => match start_result {
-                    Ok(c) => running_relaxed.push(c),
-                    Err(e) => {
-                        event!(
-                            Level::ERROR,
-                            "starting relaxed container result error: {}",
-                            e
-                        );
-                        if first_error.is_none() {
-                            first_error = Some(e);
-                        }
-                    }
-                },
-                Err(_) => {
-                    event!(Level::ERROR, "join errror on gathering relaxed containers");
-                    if first_error.is_none() {
-                        first_error = Some(DockerTestError::Processing(
-                            "join error gathering".to_string(),
-                        ));
+        if !failures.is_empty() {
+            // Report all containers that were never started due to a failed dependency.
+            for (i, transitional) in self.phase.kept.iter().enumerate() {
+                if let Transitional::Pending(p) = transitional {
+                    if remaining[i] == 0 {
+                        continue;
                     }
+                    let failed: Vec<String> = dependencies[i]
+                        .iter()
+                        .flat_map(|d| match &self.phase.kept[*d] {
+                            Transitional::Pending(p) => Some(format!("`{}`", p.handle)),
+                            Transitional::CreationFailure(f) => Some(format!("`{}`", f.handle)),
+                            _ => None,
+                        })
+                        .collect();
+                    let err = DockerTestError::Startup(format!(
+                        "not started due to dependencies not being ready: {}",
+                        failed.join(", ")
+                    ));
+                    failures.push(ContainerFailure::new(
+                        &p.handle,
+                        LifecyclePhase::Startup,
+                        err,
+                    ));
                 }
             }
+
+            return Err(failures);
+        }
+
+        for started in STATIC_CONTAINERS.external_containers().await.into_iter() {
+            // Locate the entry into kept of the external container
+            let position = match self.phase.kept.iter().position(|x| match x {
+                Transitional::StaticExternal(e) => e.handle == started.handle,
+                _ => false,
+            }) {
+                Some(e) => e,
+                None => continue,
+            };
+
+            self.phase.kept[position] = Transitional::Running(started);
         }
 
+        Ok(())
+    }
+
+    // Implementation detail
+    // A container that ultimately failed to start is returned alongside its error, since
+    // retrying may have replaced the container we were given. No container is returned if
+    // recreating it failed.
+    fn start_container(
+        index: usize,
+        container: PendingContainer,
+        deadline: Option<(Instant, Duration)>,
+    ) -> impl Future<Output = (usize, Vec<SystemTime>, StartOutcome)> {
         event!(
             Level::TRACE,
-            "finished waiting for started relaxed containers with result: {}",
-            first_error.is_none()
+            "starting container `{}` with start policy {:?}",
+            container.handle,
+            container.start_policy
         );
 
-        match first_error {
-            None => Ok(running_relaxed),
-            Some(e) => Err(e),
+        let given = container.clone();
+        let join_handle = tokio::spawn(container.start_with_retry(deadline));
+        async move {
+            let (retries, result) = match join_handle.await {
+                Ok(outcome) => outcome,
+                Err(_) => {
+                    event!(Level::ERROR, "join errror on gathering started container");
+                    let e = DockerTestError::Processing("join error gathering".to_string());
+                    (Vec::new(), Err((Some(given), e)))
+                }
+            };
+            (index, retries, result)
         }
     }
 
-    // QUESTION: Create a structured object with metadata from the composition for
-    // the object representation a creation failure?
-    pub fn creation_failures(&self) -> Vec<DockerTestError> {
+    pub fn creation_failures(&self) -> Vec<ContainerFailure> {
         self.phase
             .kept
             .iter()
@@ -428,7 +611,7 @@ impl Engine<Igniting> {
                     external.push(s);
                     None
                 }
-                Transitional::Sentinel | Transitional::CreationFailure(_) => None,
+                Transitional::CreationFailure(_) => None,
             })
             .collect();
 
@@ -462,16 +645,25 @@ impl Engine<Orbiting> {
         }
     }
 
+    /// Retrieve the handles of all running containers.
+    pub fn running_handles(&self) -> Vec<String> {
+        self.phase
+            .kept
+            .iter()
+            .flat_map(|x| match x {
+                Transitional::Running(r) => Some(r.handle.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Query whether or not the provided handle resolve to conflicting containers.
     pub fn handle_collision(&self, handle: &str) -> bool {
         self.keeper.lookup_collisions.contains(handle)
     }
 
     pub fn resolve_handle(&self, handle: &str) -> Option<&RunningContainer> {
-        let index = match self.keeper.lookup_handlers.get(handle) {
-            None => return None,
-            Some(i) => i,
-        };
+        let index = self.keeper.lookup_handlers.get(handle)?;
 
         match &self.phase.kept[*index] {
             Transitional::Running(r) => Some(r),
@@ -480,16 +672,17 @@ impl Engine<Orbiting> {
         }
     }
 
-    pub async fn inspect(
-        &mut self,
-        client: &Docker,
-        network_name: &str,
-    ) -> Result<(), Vec<DockerTestError>> {
+    pub async fn inspect(&mut self, network_name: &str) -> Result<(), Vec<ContainerFailure>> {
         // TODO: Run the inspect operation in paralell with futures, and join_all
         // Need to figure out how to best update their state in their future.
 
         let mut errors = Vec::new();
-        for transitional in self.phase.kept.iter_mut() {
+        for (transitional, report) in self
+            .phase
+            .kept
+            .iter_mut()
+            .zip(self.keeper.reports.iter_mut())
+        {
             // Ensure that we have a RunningContainer
             let container = match transitional {
                 Transitional::Running(r) => r,
@@ -497,75 +690,62 @@ impl Engine<Orbiting> {
                 _ => continue,
             };
 
-            // On Windows container IPs cannot be resolved from outside a container.
-            // So container IPs in the test body are useless and the only way to contact a
-            // container is through a port map and localhost.
-            // To avoid have users to have cfg!(windows) in their test bodies, we simply set all
-            // container ips to localhost
-            //
-            // TODO: Find another strategy to contact containers from the test body on Windows.
-            if cfg!(windows) {
-                container.ip = std::net::Ipv4Addr::new(127, 0, 0, 1);
+            if let Err(e) = container.inspect(network_name).await {
+                errors.push(ContainerFailure::new(
+                    &container.handle,
+                    LifecyclePhase::Inspect,
+                    e,
+                ));
                 continue;
             }
-            let details = match client
-                .inspect_container(&container.id, None::<InspectContainerOptions>)
-                .await
-            {
-                Ok(details) => details,
-                Err(e) => {
-                    let err =
-                        DockerTestError::Daemon(format!("failed to inspect container: {}", e));
-                    errors.push(err);
-                    continue;
-                }
-            };
 
-            // Get the ip address from the network
-            container.ip = if let Some(inspected_network) = details
-                .network_settings
-                .as_ref()
-                .unwrap()
-                .networks
-                .as_ref()
-                .unwrap()
-                .get(network_name)
-            {
-                event!(
-                    Level::DEBUG,
-                    "container ip from inspect: {}",
-                    inspected_network.ip_address.as_ref().unwrap()
-                );
-                inspected_network
-                    .ip_address
-                    .as_ref()
-                    .unwrap()
-                    .parse::<std::net::Ipv4Addr>()
-                    // Exited containers will not have an IP address
-                    .unwrap_or_else(|e| {
-                        event!(Level::TRACE, "container ip address failed to parse: {}", e);
-                        std::net::Ipv4Addr::UNSPECIFIED
-                    })
-            } else {
-                std::net::Ipv4Addr::UNSPECIFIED
-            };
+            report.transitions.push(Transition::now("inspected"));
+        }
 
-            container.ports = if let Some(ports) = details.network_settings.unwrap().ports {
-                event!(
-                    Level::DEBUG,
-                    "container ports from inspect: {:?}",
-                    ports.clone()
-                );
-                match HostPortMappings::try_from(ports) {
-                    Ok(h) => h,
-                    Err(e) => {
-                        let err = DockerTestError::HostPort(e.to_string());
-                        errors.push(err);
-                        continue;
-                    }
-                }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Invoke the `on_ready` hook of each running container concurrently.
+    ///
+    /// The hook of a static container is only invoked once across all tests.
+    pub async fn run_on_ready(&mut self) -> Result<(), Vec<ContainerFailure>> {
+        let hooks = self
+            .phase
+            .kept
+            .iter()
+            .zip(self.keeper.on_ready.iter())
+            .enumerate()
+            .filter_map(|(i, (transitional, hook))| match (transitional, hook) {
+                (Transitional::Running(r), Some(hook)) => Some(async move {
+                    event!(Level::DEBUG, "invoking on_ready hook of `{}`", r.handle);
+                    let result = if r.is_static {
+                        STATIC_CONTAINERS.on_ready_once(r, hook).await
+                    } else {
+                        hook.call(r.clone()).await
+                    };
+                    let result = result
+                        .map_err(|e| ContainerFailure::new(&r.handle, LifecyclePhase::OnReady, e));
+                    (i, result, SystemTime::now())
+                }),
+                _ => None,
+            });
+
+        let mut errors = Vec::new();
+        for (i, result, time) in join_all(hooks).await {
+            let phase = if result.is_ok() {
+                "on_ready"
             } else {
-                HostPortMappings::default()
+                "on_ready_failed"
+            };
+            self.keeper.reports[i]
+                .transitions
+                .push(Transition::at(phase, time));
+            if let Err(e) = result {
+                errors.push(e);
             }
         }
 
@@ -577,6 +757,61 @@ impl Engine<Orbiting> {
     }
 }
 
+/// Locate a cycle in the dependency graph, if any.
+///
+/// The returned path starts and ends with the same index, where each index depends on the
+/// next one in the path.
+fn find_cycle(dependencies: &[Vec<usize>]) -> Option<Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+
+    fn visit(
+        node: usize,
+        dependencies: &[Vec<usize>],
+        marks: &mut [Mark],
+        path: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        marks[node] = Mark::InProgress;
+        path.push(node);
+
+        for dep in dependencies[node].iter() {
+            match marks[*dep] {
+                Mark::InProgress => {
+                    let start = path.iter().position(|n| n == dep).unwrap();
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(*dep);
+                    return Some(cycle);
+                }
+                Mark::Unvisited => {
+                    if let Some(cycle) = visit(*dep, dependencies, marks, path) {
+                        return Some(cycle);
+                    }
+                }
+                Mark::Done => (),
+            }
+        }
+
+        path.pop();
+        marks[node] = Mark::Done;
+        None
+    }
+
+    let mut marks = vec![Mark::Unvisited; dependencies.len()];
+    for node in 0..dependencies.len() {
+        if marks[node] == Mark::Unvisited {
+            if let Some(cycle) = visit(node, dependencies, &mut marks, &mut Vec::new()) {
+                return Some(cycle);
+            }
+        }
+    }
+
+    None
+}
+
 impl Engine<Debris> {
     /// Handle container logs during test execution.
     ///
@@ -684,7 +919,42 @@ impl Engine<Debris> {
             .await;
     }
 
-    pub async fn stop_containers(self, client: &Docker) {
+    /// Retrieve the lifecycle report of each container.
+    pub fn container_reports(&self) -> Vec<ContainerReport> {
+        self.keeper.reports.clone()
+    }
+
+    /// Retrieve the ids of all containers whose lifecycle is managed by this test.
+    pub fn managed_container_ids(&self) -> Vec<String> {
+        self.phase
+            .kept
+            .iter()
+            .filter(|c| !c.is_static())
+            .map(|c| c.id.clone())
+            .collect()
+    }
+
+    /// Unpause all containers left paused by the test body, as paused containers can neither
+    /// be stopped nor removed.
+    ///
+    /// Each container is inspected, since it may have been paused or unpaused by any means
+    /// throughout the test body.
+    pub async fn unpause_containers(&self, client: &Docker) -> Vec<DockerTestError> {
+        let cleanup: Vec<&CleanupContainer> =
+            self.phase.kept.iter().filter(|c| !c.is_static()).collect();
+
+        let results = join_all(
+            cleanup
+                .iter()
+                .map(|c| unpause_if_paused(client, &c.id))
+                .collect::<Vec<_>>(),
+        )
+        .await;
+
+        teardown_errors(cleanup, results, "unpause")
+    }
+
+    pub async fn stop_containers(self, client: &Docker) -> Vec<DockerTestError> {
         let cleanup: Vec<CleanupContainer> = self
             .phase
             .kept
@@ -692,17 +962,19 @@ impl Engine<Debris> {
             .filter(|c| !c.is_static())
             .collect();
 
-        join_all(
+        let results = join_all(
             cleanup
                 .iter()
                 .map(|c| client.stop_container(&c.id, None::<StopContainerOptions>))
                 .collect::<Vec<_>>(),
         )
         .await;
+
+        teardown_errors(&cleanup, results, "stop")
     }
 
     /// The container must be removed prior to removing volumes.
-    pub async fn remove_containers(self, client: &Docker) {
+    pub async fn remove_containers(self, client: &Docker) -> Vec<DockerTestError> {
         let cleanup: Vec<CleanupContainer> = self
             .phase
             .kept
@@ -726,6 +998,113 @@ impl Engine<Debris> {
                 client.remove_container(&c.id, options)
             })
             .collect::<Vec<_>>();
-        join_all(futures).await;
+        let results = join_all(futures).await;
+
+        teardown_errors(&cleanup, results, "remove")
+    }
+}
+
+/// Collect the failed teardown operations performed on each container.
+fn teardown_errors<'a>(
+    containers: impl IntoIterator<Item = &'a CleanupContainer>,
+    results: Vec<Result<(), bollard::errors::Error>>,
+    operation: &str,
+) -> Vec<DockerTestError> {
+    containers
+        .into_iter()
+        .zip(results)
+        .filter_map(|(c, result)| match result {
+            // The container was already stopped or removed.
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 304 | 404,
+                ..
+            }) => None,
+            result => result.err().map(|e| {
+                event!(
+                    Level::ERROR,
+                    "failed to {} container `{}`: {}",
+                    operation,
+                    c.name,
+                    e
+                );
+                DockerTestError::Teardown(format!(
+                    "failed to {} container `{}`: {}",
+                    operation, c.name, e
+                ))
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::composition::Composition;
+    use crate::engine::bootstrap;
+    use crate::{DockerTestError, StartPolicy};
+
+    fn composition(handle: &str) -> Composition {
+        Composition::with_repository("dockertest-rs/hello").with_container_name(handle)
+    }
+
+    // Explicit dependencies are resolved into indices, and strict compositions implicitly
+    // depend on the previous strict composition.
+    #[test]
+    fn test_resolve_dependencies() {
+        let db = composition("db");
+        let cache = composition("cache").with_start_policy(StartPolicy::Strict);
+        let mut api = composition("api").with_start_policy(StartPolicy::Strict);
+        api.depends_on("db");
+
+        let mut engine = bootstrap(vec![db, cache, api]).fuel();
+        engine
+            .resolve_dependencies()
+            .expect("failed to resolve dependencies");
+
+        assert_eq!(
+            engine.keeper.dependencies,
+            vec![vec![], vec![], vec![0, 1]],
+            "wrong dependency graph resolved"
+        );
+    }
+
+    // A dependency cycle is rejected, reporting the handles involved.
+    #[test]
+    fn test_resolve_dependencies_cycle() {
+        let mut db = composition("db");
+        db.depends_on("api");
+        let mut cache = composition("cache");
+        cache.depends_on("db");
+        let mut api = composition("api");
+        api.depends_on("cache");
+
+        let mut engine = bootstrap(vec![db, cache, api]).fuel();
+        let result = engine.resolve_dependencies();
+
+        assert_eq!(
+            result,
+            Err(DockerTestError::DependencyCycle {
+                cycle: vec![
+                    "db".to_string(),
+                    "api".to_string(),
+                    "cache".to_string(),
+                    "db".to_string()
+                ]
+            }),
+            "dependency cycle was not detected"
+        );
+    }
+
+    // A dependency on an unknown handle is rejected.
+    #[test]
+    fn test_resolve_dependencies_unknown_handle() {
+        let mut api = composition("api");
+        api.depends_on("db");
+
+        let mut engine = bootstrap(vec![api]).fuel();
+
+        assert!(
+            engine.resolve_dependencies().is_err(),
+            "dependency on unknown handle should be rejected"
+        );
     }
 }
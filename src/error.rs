@@ -1,5 +1,8 @@
 //! Errors that can arise from dockertest.
 
+use crate::container::ExecOutput;
+
+use std::time::Duration;
 use thiserror::Error;
 
 /// Public library error conditions.
@@ -28,4 +31,279 @@ pub enum DockerTestError {
     LogWriteError(String),
     #[error("host port error `{0}`")]
     HostPort(String),
+    #[error("container dependency cycle detected `{}`", .cycle.join(" -> "))]
+    DependencyCycle { cycle: Vec<String> },
+    #[error("{}", display_container_failures(.0))]
+    ContainerFailures(Vec<ContainerFailure>),
+    #[error("{}", display_startup_attempts(.0))]
+    StartupAttempts(Vec<DockerTestError>),
+    #[error("{}", display_validation_errors(.0))]
+    Validation(Vec<ValidationError>),
+    #[error("test body timed out after {timeout:?}, running containers: `{}`", .containers.join("`, `"))]
+    TestTimeout {
+        timeout: Duration,
+        containers: Vec<String>,
+    },
+    #[error("`{operation}` is not permitted on static container `{handle}`")]
+    StaticContainer { handle: String, operation: String },
+    #[error("container `{handle}` did not exit within {timeout:?}")]
+    ExitTimeout { handle: String, timeout: Duration },
+    #[error(
+        "`{cmd}` in container `{handle}` did not exit with code {expected_exit_code} within {timeout:?}{}",
+        display_exec_attempts(.last, .last_error)
+    )]
+    ExecWaitTimeout {
+        handle: String,
+        cmd: String,
+        expected_exit_code: i64,
+        timeout: Duration,
+        last: Option<Box<ExecOutput>>,
+        last_error: Option<Box<DockerTestError>>,
+    },
+    #[error("exec of `{cmd}` in container `{handle}` timed out after {timeout:?}")]
+    ExecTimeout {
+        handle: String,
+        cmd: String,
+        timeout: Duration,
+    },
+}
+
+/// The failure of a single container, attributed to the lifecycle phase it failed in.
+#[derive(Error, Debug, PartialEq, Clone, Eq)]
+#[error("container `{handle}` failed during {phase}: {error}")]
+pub struct ContainerFailure {
+    /// The handle of the failed container.
+    pub handle: String,
+    /// The lifecycle phase the container failed in.
+    pub phase: LifecyclePhase,
+    /// The error condition of the failure.
+    pub error: DockerTestError,
+}
+
+/// A problem with the configuration of a [DockerTest], detected prior to running the test.
+///
+/// [DockerTest]: crate::DockerTest
+#[derive(Error, Debug, PartialEq, Clone, Eq)]
+pub enum ValidationError {
+    /// Multiple containers share the same handle.
+    #[error("handle `{handle}` is used by multiple containers")]
+    DuplicateHandle {
+        /// The shared handle.
+        handle: String,
+    },
+    /// A container injects the container name of a handle that does not exist.
+    #[error("container `{handle}` injects the container name of unknown handle `{target}`")]
+    UnknownInjectHandle {
+        /// The handle of the injecting container.
+        handle: String,
+        /// The unknown handle.
+        target: String,
+    },
+    /// A container depends on a handle that does not exist.
+    #[error("container `{handle}` depends on unknown handle `{target}`")]
+    UnknownDependencyHandle {
+        /// The handle of the dependent container.
+        handle: String,
+        /// The unknown handle.
+        target: String,
+    },
+    /// The same host port is bound multiple times.
+    #[error("host port `{port}` is bound multiple times by `{}`", .handles.join("`, `"))]
+    DuplicateHostPort {
+        /// The host port.
+        port: String,
+        /// The handle of the container for each binding of the port.
+        handles: Vec<String>,
+    },
+    /// The final name of a container would be rejected by the docker daemon.
+    #[error("container `{handle}` has invalid container name `{name}`")]
+    InvalidContainerName {
+        /// The handle of the container.
+        handle: String,
+        /// The invalid container name.
+        name: String,
+    },
+    /// An externally managed container does not specify the name of the container.
+    #[error("external container `{handle}` does not specify a container name")]
+    MissingExternalName {
+        /// The handle of the container.
+        handle: String,
+    },
+}
+
+/// The lifecycle phases of a container where a [ContainerFailure] may occur.
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum LifecyclePhase {
+    /// Creating the container on the docker daemon.
+    Creation,
+    /// Starting the container and awaiting its `WaitFor` condition.
+    Startup,
+    /// Inspecting the started container for its runtime information.
+    Inspect,
+    /// Invoking the `on_ready` hook of the ready container.
+    OnReady,
+}
+
+impl std::fmt::Display for LifecyclePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let phase = match self {
+            LifecyclePhase::Creation => "creation",
+            LifecyclePhase::Startup => "startup",
+            LifecyclePhase::Inspect => "inspect",
+            LifecyclePhase::OnReady => "on_ready hook",
+        };
+        write!(f, "{}", phase)
+    }
+}
+
+impl ContainerFailure {
+    pub(crate) fn new<T: ToString>(
+        handle: T,
+        phase: LifecyclePhase,
+        error: DockerTestError,
+    ) -> ContainerFailure {
+        ContainerFailure {
+            handle: handle.to_string(),
+            phase,
+            error,
+        }
+    }
+}
+
+fn display_container_failures(failures: &[ContainerFailure]) -> String {
+    let mut display = format!("{} container failure(s)", failures.len());
+    for failure in failures.iter() {
+        display.push_str(&format!("\n- {}", failure));
+    }
+    display
+}
+
+fn display_validation_errors(errors: &[ValidationError]) -> String {
+    let mut display = format!("{} configuration problem(s)", errors.len());
+    for error in errors.iter() {
+        display.push_str(&format!("\n- {}", error));
+    }
+    display
+}
+
+fn display_startup_attempts(attempts: &[DockerTestError]) -> String {
+    let mut display = format!("all {} startup attempts failed", attempts.len());
+    for (i, attempt) in attempts.iter().enumerate() {
+        display.push_str(&format!("\n  - attempt {}: {}", i + 1, attempt));
+    }
+    display
+}
+
+fn display_exec_attempts(
+    last: &Option<Box<ExecOutput>>,
+    last_error: &Option<Box<DockerTestError>>,
+) -> String {
+    let mut display = match last {
+        Some(output) => format!(
+            ", last attempt exited with {}, stdout: `{}`, stderr: `{}`",
+            output.exit_code,
+            output.stdout_lossy().trim(),
+            output.stderr_lossy().trim()
+        ),
+        None => ", no attempt completed".to_string(),
+    };
+    if let Some(error) = last_error {
+        display.push_str(&format!(", last failed attempt: {}", error));
+    }
+    display
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{ContainerFailure, DockerTestError, LifecyclePhase};
+    use crate::ExecOutput;
+
+    use std::time::Duration;
+
+    // Every container failure is listed with its handle and lifecycle phase.
+    #[test]
+    fn test_container_failures_display() {
+        let error = DockerTestError::ContainerFailures(vec![
+            ContainerFailure::new(
+                "db",
+                LifecyclePhase::Creation,
+                DockerTestError::Daemon("oops".to_string()),
+            ),
+            ContainerFailure::new(
+                "api",
+                LifecyclePhase::Startup,
+                DockerTestError::Startup("not ready".to_string()),
+            ),
+        ]);
+
+        assert_eq!(
+            error.to_string(),
+            "2 container failure(s)\n\
+             - container `db` failed during creation: docker daemon interaction error `oops`\n\
+             - container `api` failed during startup: startup condition not fulfilled `not ready`",
+            "container failures not displayed correctly"
+        );
+    }
+
+    // Every failed startup attempt is listed in order.
+    #[test]
+    fn test_startup_attempts_display() {
+        let error = DockerTestError::StartupAttempts(vec![
+            DockerTestError::Startup("not ready".to_string()),
+            DockerTestError::Daemon("oops".to_string()),
+        ]);
+
+        assert_eq!(
+            error.to_string(),
+            "all 2 startup attempts failed\n  \
+             - attempt 1: startup condition not fulfilled `not ready`\n  \
+             - attempt 2: docker daemon interaction error `oops`",
+            "startup attempts not displayed correctly"
+        );
+    }
+
+    // The output of the last exec attempt is included.
+    #[test]
+    fn test_exec_wait_timeout_display() {
+        let error = DockerTestError::ExecWaitTimeout {
+            handle: "db".to_string(),
+            cmd: "pg_isready".to_string(),
+            expected_exit_code: 0,
+            timeout: Duration::from_secs(5),
+            last: Some(Box::new(ExecOutput {
+                stdout: b"/var/run/postgresql:5432 - no response\n".to_vec(),
+                stderr: Vec::new(),
+                exit_code: 2,
+            })),
+            last_error: None,
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "`pg_isready` in container `db` did not exit with code 0 within 5s, last attempt \
+             exited with 2, stdout: `/var/run/postgresql:5432 - no response`, stderr: ``"
+        );
+    }
+
+    // The error of the last attempt that failed to execute the command is included.
+    #[test]
+    fn test_exec_wait_timeout_display_failed_attempt() {
+        let error = DockerTestError::ExecWaitTimeout {
+            handle: "db".to_string(),
+            cmd: "pg_isready".to_string(),
+            expected_exit_code: 0,
+            timeout: Duration::from_secs(5),
+            last: None,
+            last_error: Some(Box::new(DockerTestError::Daemon(
+                "container is not running".to_string(),
+            ))),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "`pg_isready` in container `db` did not exit with code 0 within 5s, no attempt \
+             completed, last failed attempt: docker daemon interaction error `container is not \
+             running`"
+        );
+    }
 }
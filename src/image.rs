@@ -1,5 +1,6 @@
 //! An Image persisted in Docker.
 
+use crate::plan::{ImagePlan, PullDecision, SourcePlan};
 use crate::DockerTestError;
 
 use bollard::{
@@ -341,6 +342,32 @@ impl Image {
         }
     }
 
+    /// Describe how this `Image` would be retrieved, without consulting the docker daemon.
+    pub(crate) fn plan(&self, default_source: &Source) -> ImagePlan {
+        let source = self.source.as_ref().unwrap_or(default_source);
+        let pull = match (source, &self.pull_policy) {
+            (Source::Local, _) | (_, PullPolicy::Never) => PullDecision::Never,
+            (_, PullPolicy::Always) => PullDecision::Always,
+            (_, PullPolicy::IfNotPresent) => PullDecision::IfNotPresent,
+        };
+
+        ImagePlan {
+            repository: self.repository.clone(),
+            tag: self.tag.clone(),
+            source: match source {
+                Source::Local => SourcePlan::Local,
+                Source::DockerHub => SourcePlan::DockerHub,
+                Source::RegistryWithCredentials(r) => SourcePlan::Registry {
+                    address: r.address.clone(),
+                },
+                Source::RegistryWithDockerLogin(address) => SourcePlan::Registry {
+                    address: address.clone(),
+                },
+            },
+            pull,
+        }
+    }
+
     /// Resolve the auth credentials based on the provided [Source].
     fn resolve_auth(&self, source: &Source) -> Result<Option<DockerCredentials>, DockerTestError> {
         let potential = match source {
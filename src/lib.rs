@@ -17,7 +17,7 @@
 //! - Named pipes (windows)
 //! - TCP with TLS
 //! - Piped through a docker-in-docker container where the execution occurs, to run on the
-//! underlying docker engine.
+//!   underlying docker engine.
 //!
 //! The main bread-and-butter of this library is the ability to specify which containers are
 //! required for a test, and how one should ensure that the container is properly running prior
@@ -65,6 +65,14 @@
 //! * [ExitedWait] - wait for the container to report _exited_ status.
 //! * [NoWait] - don't wait for anything
 //! * [MessageWait] - wait for the following message to appear in the log stream.
+//! * [LogMatcherWait] - wait for log lines matching a [LogMatcher] to appear in the log stream.
+//! * [HealthyWait] - wait for the healthcheck of the container to report _healthy_ status.
+//! * [TcpPortWait] - wait for a container port to accept TCP connections.
+//! * `HttpWait` - wait for an HTTP endpoint of the container to respond as expected, requires
+//!   the `http` feature.
+//! * [ExecWait] - wait for a command executed within the container to succeed.
+//!
+//! These conditions may be composed through the [AllOf], [AnyOf] and [Sequence] combinators.
 //!
 //! # Environment variables
 //!
@@ -80,6 +88,18 @@
 //! * `stop_on_failure`: stop containers on execution failure
 //! * `running_on_failure`: leave containers running on execution failure
 //!
+//! The prune policy is also applied if the test process is interrupted by SIGINT or SIGTERM,
+//! where the interrupted test is considered a failure. This covers all containers, isolated
+//! networks and named volumes created by dockertest, including internally managed static
+//! containers.
+//!
+//! ## Run reports
+//!
+//! Setting `DOCKERTEST_REPORT_DIR` to a directory writes a JSON report for each test run into
+//! that directory, named `{namespace}-{id}.json`. Each report contains the test outcome, the
+//! timestamp of each phase per container, and any teardown errors. A single test can instead be
+//! configured through `DockerTest::with_report_path`.
+//!
 //! ## Dockertest in Docker
 //!
 //! If the execution environment of running dockertest is itself a docker-in-docker container, one
@@ -98,7 +118,6 @@
 //! use dockertest::{TestBodySpecification, DockerTest};
 //! use std::sync::{Arc, Mutex};
 //!
-//! #[test]
 //! fn hello_world_test() {
 //!     // Define our test instance
 //!     let mut test = DockerTest::new();
@@ -137,6 +156,14 @@
 //! [ExitedWait]: crate::waitfor::ExitedWait
 //! [NoWait]: crate::waitfor::NoWait
 //! [MessageWait]: crate::waitfor::MessageWait
+//! [LogMatcherWait]: crate::waitfor::LogMatcherWait
+//! [HealthyWait]: crate::waitfor::HealthyWait
+//! [TcpPortWait]: crate::waitfor::TcpPortWait
+//! [ExecWait]: crate::waitfor::ExecWait
+//! [AllOf]: crate::waitfor::AllOf
+//! [AnyOf]: crate::waitfor::AnyOf
+//! [Sequence]: crate::waitfor::Sequence
+//! [LogMatcher]: crate::waitfor::LogMatcher
 
 mod composition;
 mod container;
@@ -144,20 +171,31 @@ mod dockertest;
 mod engine;
 mod error;
 mod image;
+mod partition;
+mod plan;
+mod report;
+mod resources;
 mod runner;
 mod specification;
 mod static_container;
+mod validation;
 // We only make this public because a function is used in our integration test
 #[doc(hidden)]
 pub mod utils;
 pub mod waitfor;
 
-pub use crate::composition::{LogAction, LogOptions, LogPolicy, LogSource, StartPolicy};
-pub use crate::container::{PendingContainer, RunningContainer};
+pub use crate::composition::{
+    HealthCheck, LogAction, LogOptions, LogPolicy, LogSource, RetryPolicy, StartPolicy,
+};
+pub use crate::container::{
+    ContainerState, ContainerStats, ContainerStatus, ExecOptions, ExecOutput, ExitStatus,
+    HealthStatus, LogLine, LogStreamOptions, PendingContainer, RunningContainer,
+};
 pub use crate::dockertest::DockerTest;
 pub use crate::dockertest::Network;
-pub use crate::error::DockerTestError;
+pub use crate::error::{ContainerFailure, DockerTestError, LifecyclePhase, ValidationError};
 pub use crate::image::{Image, PullPolicy, RegistryCredentials, Source};
+pub use crate::plan::{ContainerPlan, ImagePlan, Plan, PortPlan, PullDecision, SourcePlan};
 pub use crate::runner::DockerOperations;
 pub use crate::specification::{
     ContainerSpecification, DynamicSpecification, ExternalSpecification, TestBodySpecification,
@@ -0,0 +1,295 @@
+//! Disconnect containers from the test network, or partition them onto separate networks,
+//! from within the test body.
+
+use crate::container::RunningContainer;
+use crate::resources::RESOURCES;
+use crate::runner::{create_network, delete_network};
+use crate::static_container::{connect_network, disconnect_network};
+use crate::DockerTestError;
+
+use bollard::{container::InspectContainerOptions, Docker};
+use tracing::{event, Level};
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// Keeps track of the containers detached from the test network by the test body, such that
+/// they are restored at teardown.
+///
+/// Shared between the [Runner](crate::runner::Runner) and each clone of the
+/// [DockerOperations](crate::DockerOperations) handed to the test body.
+#[derive(Clone)]
+pub(crate) struct Partitions {
+    /// The network of the test, which is referenced by ID in the singular network mode.
+    network: String,
+    /// ID of the DockerTest instance, suffixed to the temporary network names.
+    id: String,
+    /// The container dockertest runs within, if any, which is connected to each temporary
+    /// network such that the test body is able to reach every partition.
+    self_container: Option<String>,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Containers detached from the test network, keyed by container id.
+    detached: HashMap<String, Detached>,
+    /// The temporary networks created to partition containers.
+    networks: Vec<String>,
+}
+
+/// A container detached from the test network.
+struct Detached {
+    /// The aliases of the container on the test network, restored once reconnected.
+    aliases: Option<Vec<String>>,
+    /// The temporary network the container was moved onto, if partitioned.
+    partition: Option<String>,
+    /// The network the container was inspected on prior to being detached, which is inspected
+    /// anew once reconnected.
+    inspected_network: Option<String>,
+}
+
+impl Partitions {
+    pub(crate) fn new(network: &str, id: &str, self_container: Option<String>) -> Partitions {
+        Partitions {
+            network: network.to_string(),
+            id: id.to_string(),
+            self_container,
+            state: Default::default(),
+        }
+    }
+
+    /// Disconnect the container from the test network, and from its partition if any.
+    pub(crate) async fn disconnect(
+        &self,
+        container: &RunningContainer,
+    ) -> Result<(), DockerTestError> {
+        container.refuse_static("disconnect")?;
+
+        self.detach(container).await?;
+        container.refresh().await
+    }
+
+    /// Reconnect a detached container to the test network, removing it from its partition.
+    pub(crate) async fn reconnect(
+        &self,
+        container: &RunningContainer,
+    ) -> Result<(), DockerTestError> {
+        container.refuse_static("reconnect")?;
+
+        let (aliases, partition, inspected_network) = match self.with_state(|s| {
+            s.detached.get(&container.id).map(|d| {
+                (
+                    d.aliases.clone(),
+                    d.partition.clone(),
+                    d.inspected_network.clone(),
+                )
+            })
+        }) {
+            Some(detached) => detached,
+            None => return Ok(()),
+        };
+
+        connect_network(&container.client, &container.id, &self.network, aliases)
+            .await
+            .map_err(|e| {
+                DockerTestError::Daemon(format!(
+                    "failed to reconnect container to the test network: {}",
+                    e
+                ))
+            })?;
+        if let Some(partition) = partition {
+            disconnect_network(&container.client, &container.id, &partition)
+                .await
+                .map_err(|e| {
+                    DockerTestError::Daemon(format!(
+                        "failed to disconnect container from partition `{}`: {}",
+                        partition, e
+                    ))
+                })?;
+        }
+        self.with_state(|s| s.detached.remove(&container.id));
+
+        match inspected_network {
+            Some(network) => container.inspect(&network).await,
+            None => container.refresh().await,
+        }
+    }
+
+    /// Move each group of containers onto its own temporary network, such that they can only
+    /// reach containers within the same group.
+    pub(crate) async fn partition(
+        &self,
+        groups: &[&[&RunningContainer]],
+    ) -> Result<(), DockerTestError> {
+        let mut seen = HashSet::new();
+        for container in groups.iter().flat_map(|g| g.iter()) {
+            container.refuse_static("partition")?;
+            if !seen.insert(&container.id) {
+                return Err(DockerTestError::TestBody(format!(
+                    "container `{}` is present in multiple partitions",
+                    container.handle
+                )));
+            }
+        }
+
+        for group in groups.iter().filter(|g| !g.is_empty()) {
+            let network = self.with_state(|s| {
+                let network = format!("dockertest-rs-{}-partition-{}", self.id, s.networks.len());
+                s.networks.push(network.clone());
+                network
+            });
+            create_network(&group[0].client, &network, self.self_container.as_deref()).await?;
+            RESOURCES.register_network(&network);
+
+            for container in group.iter() {
+                let aliases = self.detach(container).await?;
+                connect_network(&container.client, &container.id, &network, aliases)
+                    .await
+                    .map_err(|e| {
+                        DockerTestError::Daemon(format!(
+                            "failed to connect container to partition `{}`: {}",
+                            network, e
+                        ))
+                    })?;
+                self.with_state(|s| {
+                    if let Some(d) = s.detached.get_mut(&container.id) {
+                        d.partition = Some(network.clone());
+                    }
+                });
+                container.inspect(&network).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reconnect all detached containers to the test network and remove the temporary networks.
+    pub(crate) async fn restore(&self, client: &Docker) -> Vec<DockerTestError> {
+        let state = self.with_state(std::mem::take);
+        let mut errors = Vec::new();
+
+        for (id, detached) in state.detached {
+            event!(
+                Level::DEBUG,
+                "reconnecting container `{}` to test network",
+                id
+            );
+            if let Err(e) = connect_network(client, &id, &self.network, detached.aliases).await {
+                errors.push(DockerTestError::Teardown(format!(
+                    "failed to reconnect container `{}` to the test network: {}",
+                    id, e
+                )));
+            }
+            if let Some(partition) = detached.partition {
+                if let Err(e) = disconnect_network(client, &id, &partition).await {
+                    event!(
+                        Level::ERROR,
+                        "unable to remove container from partition `{}`: {}",
+                        partition,
+                        e
+                    );
+                }
+            }
+        }
+
+        for network in state.networks {
+            if let Err(e) = delete_network(client, &network, self.self_container.as_deref()).await {
+                errors.push(e);
+            }
+            RESOURCES.release_network(&network);
+        }
+
+        errors
+    }
+
+    /// Detach the container from both the test network and its partition, if any, returning
+    /// its aliases on the test network.
+    async fn detach(
+        &self,
+        container: &RunningContainer,
+    ) -> Result<Option<Vec<String>>, DockerTestError> {
+        let detached = self.with_state(|s| {
+            s.detached
+                .get(&container.id)
+                .map(|d| (d.aliases.clone(), d.partition.clone()))
+        });
+
+        match detached {
+            Some((aliases, Some(partition))) => {
+                disconnect_network(&container.client, &container.id, &partition)
+                    .await
+                    .map_err(|e| {
+                        DockerTestError::Daemon(format!(
+                            "failed to disconnect container from partition `{}`: {}",
+                            partition, e
+                        ))
+                    })?;
+                self.with_state(|s| {
+                    if let Some(d) = s.detached.get_mut(&container.id) {
+                        d.partition = None;
+                    }
+                });
+                Ok(aliases)
+            }
+            Some((aliases, None)) => Ok(aliases),
+            None => {
+                let aliases = self.aliases(container).await?;
+                disconnect_network(&container.client, &container.id, &self.network)
+                    .await
+                    .map_err(|e| {
+                        DockerTestError::Daemon(format!(
+                            "failed to disconnect container from the test network: {}",
+                            e
+                        ))
+                    })?;
+                self.with_state(|s| {
+                    s.detached.insert(
+                        container.id.clone(),
+                        Detached {
+                            aliases: aliases.clone(),
+                            partition: None,
+                            inspected_network: container.inspected().network,
+                        },
+                    )
+                });
+                Ok(aliases)
+            }
+        }
+    }
+
+    /// Retrieve the aliases of the container on the test network.
+    async fn aliases(
+        &self,
+        container: &RunningContainer,
+    ) -> Result<Option<Vec<String>>, DockerTestError> {
+        let details = container
+            .client
+            .inspect_container(&container.id, None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| DockerTestError::Daemon(format!("failed to inspect container: {}", e)))?;
+
+        // The networks of a container are keyed by name, whereas the singular network is
+        // referenced by ID.
+        let aliases = details
+            .network_settings
+            .and_then(|n| n.networks)
+            .and_then(|networks| {
+                networks.into_iter().find_map(|(name, endpoint)| {
+                    let network_id = endpoint.network_id.as_deref();
+                    if name == self.network || network_id == Some(self.network.as_str()) {
+                        endpoint.aliases
+                    } else {
+                        None
+                    }
+                })
+            });
+
+        Ok(aliases)
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut State) -> T) -> T {
+        let mut state = self.state.lock().expect("failed to get partitions lock");
+        f(&mut state)
+    }
+}
@@ -0,0 +1,217 @@
+//! Describe what a test run would do, without interacting with the docker daemon.
+
+use crate::engine::bootstrap;
+use crate::runner::suffix_named_volumes;
+use crate::static_container::SCOPED_NETWORKS;
+use crate::utils::generate_random_string;
+use crate::{DockerTest, DockerTestError, Network, StartPolicy};
+
+use serde::Serialize;
+
+use std::collections::BTreeMap;
+
+/// A description of the environment a [DockerTest] would create when run.
+///
+/// The random suffix of each container name, volume name and isolated network name is
+/// generated anew for each plan, and will thus differ from the names used by an actual run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Plan {
+    /// The namespace prefixing each container name.
+    pub namespace: String,
+    /// The name of the docker network the containers are attached to.
+    pub network: String,
+    /// The final names of all named volumes that would be created.
+    pub volumes: Vec<String>,
+    /// Handles that are used by multiple containers, and thus cannot be resolved.
+    pub handle_collisions: Vec<String>,
+    /// Every container that would be created, in the order they were provided.
+    pub containers: Vec<ContainerPlan>,
+}
+
+/// A description of a single container of a [Plan].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ContainerPlan {
+    /// The handle used to reference the container within the test body.
+    pub handle: String,
+    /// The final name of the container.
+    pub name: String,
+    /// The image the container would be created from.
+    pub image: ImagePlan,
+    /// The start policy of the container.
+    pub start_policy: StartPolicy,
+    /// The named volumes mounted into the container, on the form `VOLUME_NAME:CONTAINER_PATH`.
+    pub named_volumes: Vec<String>,
+    /// The environment variables injected with the name of another container.
+    pub injected_env: BTreeMap<String, String>,
+    /// The host port bindings of the container.
+    pub ports: Vec<PortPlan>,
+    /// Whether all exposed ports are published to ephemeral host ports.
+    pub publish_all_ports: bool,
+}
+
+/// A host port binding of a [ContainerPlan].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PortPlan {
+    /// The exposed container port and protocol, e.g. `8080/tcp`.
+    pub exposed: String,
+    /// The host port the exposed port is bound to.
+    pub host: String,
+}
+
+/// The image of a [ContainerPlan], and how it would be retrieved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ImagePlan {
+    /// The repository of the image.
+    pub repository: String,
+    /// The tag of the image.
+    pub tag: String,
+    /// Where the image would be retrieved from.
+    pub source: SourcePlan,
+    /// Whether the image would be pulled.
+    pub pull: PullDecision,
+}
+
+/// Where the image of an [ImagePlan] would be retrieved from.
+///
+/// Registry credentials are never part of the plan.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourcePlan {
+    /// The local docker daemon storage.
+    Local,
+    /// The official docker registry.
+    DockerHub,
+    /// A custom registry at the given address.
+    Registry {
+        /// The address of the registry.
+        address: String,
+    },
+}
+
+/// Whether the image of an [ImagePlan] would be pulled.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullDecision {
+    /// The image is always pulled.
+    Always,
+    /// The image is only pulled if it does not exist on the local docker daemon.
+    IfNotPresent,
+    /// The image is never pulled, and must exist on the local docker daemon.
+    Never,
+}
+
+/// Resolve the [Plan] of the given [DockerTest].
+///
+/// This performs the same resolution steps as an actual run, up until the first interaction
+/// with the docker daemon.
+pub(crate) fn resolve(config: &DockerTest) -> Result<Plan, DockerTestError> {
+    let id = generate_random_string(20);
+
+    let mut compositions = config.compositions.clone();
+    let mut volumes = suffix_named_volumes(&mut compositions, &id);
+    volumes.sort();
+
+    let mut engine = bootstrap(compositions);
+    engine.resolve_final_container_name(&config.namespace);
+
+    let mut engine = engine.fuel();
+    engine.resolve_inject_container_name_env()?;
+    engine.resolve_dependencies()?;
+
+    let network = match &config.network {
+        Network::Singular => SCOPED_NETWORKS.name(&config.namespace),
+        Network::External(n) => n.clone(),
+        Network::Isolated => format!("dockertest-rs-{}", id),
+    };
+
+    Ok(Plan {
+        namespace: config.namespace.clone(),
+        network,
+        volumes,
+        handle_collisions: engine.handle_collisions(),
+        containers: engine.plan_containers(&config.default_source),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plan::{PortPlan, PullDecision, SourcePlan};
+    use crate::{DockerTest, Image, Network, PullPolicy, Source, TestBodySpecification};
+
+    // The plan resolves names, volumes, injected environment variables and port maps.
+    #[test]
+    fn test_plan_resolves_environment() {
+        let mut db = TestBodySpecification::with_repository("postgres").set_handle("db");
+        db.modify_named_volume("data", "/var/lib/postgresql/data")
+            .modify_port_map(5432, 15432);
+        let mut api = TestBodySpecification::with_image(
+            Image::with_repository("org/api").pull_policy(PullPolicy::Always),
+        );
+        api.inject_container_name("db", "DB_HOST")
+            .modify_named_volume("data", "/data");
+
+        let mut test = DockerTest::new()
+            .with_namespace("ns")
+            .with_network(Network::External("external".to_string()))
+            .with_default_source(Source::DockerHub);
+        test.provide_container(db).provide_container(api);
+
+        let plan = test.plan().expect("failed to plan test");
+        assert_eq!(plan.network, "external");
+        assert_eq!(plan.volumes.len(), 1, "named volume was not shared");
+        assert!(plan.handle_collisions.is_empty());
+
+        let db = &plan.containers[0];
+        let api = &plan.containers[1];
+        assert!(db.name.starts_with("ns-db-"), "wrong name `{}`", db.name);
+        assert!(
+            api.name.starts_with("ns-org_api-"),
+            "wrong name `{}`",
+            api.name
+        );
+        assert_eq!(
+            db.named_volumes,
+            vec![format!("{}:/var/lib/postgresql/data", plan.volumes[0])]
+        );
+        assert_eq!(
+            api.named_volumes,
+            vec![format!("{}:/data", plan.volumes[0])]
+        );
+        assert_eq!(api.injected_env.get("DB_HOST"), Some(&db.name));
+        assert_eq!(
+            db.ports,
+            vec![PortPlan {
+                exposed: "5432/tcp".to_string(),
+                host: "15432".to_string(),
+            }]
+        );
+        assert_eq!(db.image.source, SourcePlan::DockerHub);
+        assert_eq!(db.image.pull, PullDecision::IfNotPresent);
+        assert_eq!(api.image.pull, PullDecision::Always);
+    }
+
+    // Handle collisions are reported, and local images are never pulled.
+    #[test]
+    fn test_plan_reports_handle_collisions() {
+        let mut test = DockerTest::new();
+        test.provide_container(TestBodySpecification::with_repository("hello-world"))
+            .provide_container(TestBodySpecification::with_repository("hello-world"));
+
+        let plan = test.plan().expect("failed to plan test");
+        assert_eq!(plan.handle_collisions, vec!["hello-world".to_string()]);
+        assert_eq!(plan.network, "dockertest-rs-dockertest");
+        assert_eq!(plan.containers[0].image.source, SourcePlan::Local);
+        assert_eq!(plan.containers[0].image.pull, PullDecision::Never);
+    }
+
+    // Injecting the name of an unknown handle fails the plan.
+    #[test]
+    fn test_plan_unknown_inject_handle() {
+        let mut api = TestBodySpecification::with_repository("api");
+        api.inject_container_name("db", "DB_HOST");
+        let mut test = DockerTest::new();
+        test.provide_container(api);
+
+        assert!(test.plan().is_err(), "unknown handle was not rejected");
+    }
+}
@@ -0,0 +1,161 @@
+//! Machine-readable report of a single test run.
+//!
+//! The report records when each container passed through each lifecycle phase, such that the
+//! overhead of container management can be attributed to pulling, creating, waiting,
+//! inspecting or tearing down. Reports are only written when enabled through
+//! [DockerTest::with_report_path] or the `DOCKERTEST_REPORT_DIR` environment variable.
+//!
+//! [DockerTest::with_report_path]: crate::DockerTest::with_report_path
+
+use crate::StartPolicy;
+
+use serde::Serialize;
+use tracing::{event, Level};
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The environment variable naming the directory to write a report per test run into.
+const REPORT_DIR_ENV: &str = "DOCKERTEST_REPORT_DIR";
+
+/// A named point in time, in milliseconds since the unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct Transition {
+    pub(crate) phase: &'static str,
+    pub(crate) unix_ms: u64,
+}
+
+impl Transition {
+    pub(crate) fn at(phase: &'static str, time: SystemTime) -> Transition {
+        let unix_ms = time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        Transition { phase, unix_ms }
+    }
+
+    pub(crate) fn now(phase: &'static str) -> Transition {
+        Transition::at(phase, SystemTime::now())
+    }
+}
+
+/// The lifecycle of a single container throughout the test run.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct ContainerReport {
+    pub(crate) handle: String,
+    pub(crate) name: String,
+    pub(crate) id: Option<String>,
+    pub(crate) image_id: Option<String>,
+    pub(crate) start_policy: StartPolicy,
+    pub(crate) transitions: Vec<Transition>,
+}
+
+/// The outcome of the test run.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Outcome {
+    /// The test body completed successfully.
+    Passed,
+    /// The test body panicked or was cancelled.
+    Failed,
+    /// The test body did not complete within the test timeout.
+    TimedOut,
+    /// The test body was never invoked, due to failing to setup the test environment.
+    SetupFailed,
+}
+
+/// The report of a single test run.
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct Report {
+    /// The id of the test run, which suffixes the resources it created.
+    pub(crate) id: String,
+    pub(crate) namespace: String,
+    pub(crate) outcome: Option<Outcome>,
+    /// The failure message of the test run, if any.
+    pub(crate) error: Option<String>,
+    /// The transitions between each phase of the test run.
+    pub(crate) phases: Vec<Transition>,
+    pub(crate) containers: Vec<ContainerReport>,
+    pub(crate) teardown_errors: Vec<String>,
+}
+
+impl Report {
+    pub(crate) fn new(id: &str, namespace: &str) -> Report {
+        Report {
+            id: id.to_string(),
+            namespace: namespace.to_string(),
+            outcome: None,
+            error: None,
+            phases: Vec::new(),
+            containers: Vec::new(),
+            teardown_errors: Vec::new(),
+        }
+    }
+
+    /// Record that the test run entered the given phase.
+    pub(crate) fn transition(&mut self, phase: &'static str) {
+        self.phases.push(Transition::now(phase));
+    }
+
+    /// Resolve where to write the report, if reporting is enabled.
+    ///
+    /// An explicitly configured path takes precedence over the environment variable.
+    pub(crate) fn destination(&self, configured: Option<&Path>) -> Option<PathBuf> {
+        match configured {
+            Some(path) => Some(path.to_path_buf()),
+            None => std::env::var_os(REPORT_DIR_ENV)
+                .map(|dir| PathBuf::from(dir).join(format!("{}-{}.json", self.namespace, self.id))),
+        }
+    }
+
+    /// Write the report to the given path.
+    ///
+    /// Failing to write the report never fails the test.
+    pub(crate) fn write(&self, path: &Path) {
+        let result = serde_json::to_vec_pretty(self)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+
+        match result {
+            Ok(_) => event!(Level::DEBUG, "wrote run report to {}", path.display()),
+            Err(e) => event!(
+                Level::WARN,
+                "failed to write run report to {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{ContainerReport, Outcome, Report, Transition};
+    use crate::StartPolicy;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    // The report serializes with snake case outcomes and millisecond timestamps.
+    #[test]
+    fn test_report_serialization() {
+        let mut report = Report::new("id", "dockertest-rs");
+        report.outcome = Some(Outcome::TimedOut);
+        report.containers.push(ContainerReport {
+            handle: "db".to_string(),
+            name: "dockertest-rs-db-id".to_string(),
+            id: Some("container-id".to_string()),
+            image_id: None,
+            start_policy: StartPolicy::Strict,
+            transitions: vec![Transition::at(
+                "created",
+                UNIX_EPOCH + Duration::from_millis(1500),
+            )],
+        });
+
+        let json = serde_json::to_value(&report).expect("failed to serialize report");
+        assert_eq!(json["outcome"], "timed_out");
+        assert_eq!(json["containers"][0]["start_policy"], "strict");
+        assert_eq!(json["containers"][0]["transitions"][0]["phase"], "created");
+        assert_eq!(json["containers"][0]["transitions"][0]["unix_ms"], 1500);
+    }
+}
@@ -0,0 +1,333 @@
+//! Process-wide registry of the docker resources created by dockertest.
+//!
+//! Teardown is performed at the end of each test, which never happens if the test process is
+//! interrupted. The registry tracks every live resource, such that they can be cleaned up in
+//! accordance with the prune strategy when the process receives SIGINT or SIGTERM.
+
+use crate::container::unpause_if_paused;
+use crate::runner::{env_prune_strategy, PruneStrategy};
+use crate::utils::connect_with_local_or_tls_defaults;
+
+use bollard::{
+    container::{RemoveContainerOptions, StopContainerOptions},
+    network::{DisconnectNetworkOptions, InspectNetworkOptions},
+    volume::RemoveVolumeOptions,
+    Docker,
+};
+use futures::future::join_all;
+use lazy_static::lazy_static;
+use tracing::{event, Level};
+
+use std::collections::HashSet;
+use std::sync::{Mutex, Once};
+
+lazy_static! {
+    pub(crate) static ref RESOURCES: Resources = Resources::default();
+}
+
+/// Keeps track of all live docker resources that dockertest is responsible for removing.
+pub(crate) struct Resources {
+    tracked: Mutex<Tracked>,
+    signal_handler: Once,
+}
+
+impl Default for Resources {
+    fn default() -> Resources {
+        Resources {
+            tracked: Mutex::new(Tracked::default()),
+            signal_handler: Once::new(),
+        }
+    }
+}
+
+/// The live docker resources.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Tracked {
+    /// Container ids.
+    containers: HashSet<String>,
+    /// Network names.
+    networks: HashSet<String>,
+    /// Volume names.
+    volumes: HashSet<String>,
+}
+
+impl Tracked {
+    fn is_empty(&self) -> bool {
+        self.containers.is_empty() && self.networks.is_empty() && self.volumes.is_empty()
+    }
+}
+
+impl Resources {
+    pub(crate) fn register_container(&self, id: &str) {
+        self.with_tracked(|t| t.containers.insert(id.to_string()));
+    }
+
+    pub(crate) fn release_container(&self, id: &str) {
+        self.with_tracked(|t| t.containers.remove(id));
+    }
+
+    pub(crate) fn register_network(&self, name: &str) {
+        self.with_tracked(|t| t.networks.insert(name.to_string()));
+    }
+
+    pub(crate) fn release_network(&self, name: &str) {
+        self.with_tracked(|t| t.networks.remove(name));
+    }
+
+    pub(crate) fn register_volumes(&self, names: &[String]) {
+        self.with_tracked(|t| t.volumes.extend(names.iter().cloned()));
+    }
+
+    pub(crate) fn release_volumes(&self, names: &[String]) {
+        self.with_tracked(|t| {
+            for n in names.iter() {
+                t.volumes.remove(n);
+            }
+        });
+    }
+
+    fn with_tracked<T>(&self, f: impl FnOnce(&mut Tracked) -> T) -> T {
+        let mut tracked = self.tracked.lock().expect("failed to get resources lock");
+        f(&mut tracked)
+    }
+
+    /// Install the process-wide signal handler, if not already installed.
+    ///
+    /// The handler lives on a dedicated thread with its own runtime, since the runtime of each
+    /// individual test is dropped once the test completes.
+    pub(crate) fn install_signal_handler(&'static self) {
+        self.signal_handler.call_once(|| {
+            let spawned = std::thread::Builder::new()
+                .name("dockertest-signal-handler".to_string())
+                .spawn(move || {
+                    let rt = match tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                    {
+                        Ok(r) => r,
+                        Err(e) => {
+                            event!(
+                                Level::WARN,
+                                "failed to allocate signal handler runtime: {}",
+                                e
+                            );
+                            return;
+                        }
+                    };
+                    rt.block_on(self.handle_signals());
+                });
+
+            if let Err(e) = spawned {
+                event!(Level::WARN, "failed to spawn signal handler thread: {}", e);
+            }
+        });
+    }
+
+    /// Await a termination signal, prune all live resources and exit the process.
+    ///
+    /// The process exits immediately if no resources are live, and on a repeated signal
+    /// during pruning.
+    async fn handle_signals(&self) {
+        let code = wait_for_signal().await;
+        if self.with_tracked(|t| t.is_empty()) {
+            std::process::exit(code);
+        }
+
+        event!(
+            Level::WARN,
+            "received termination signal, cleaning up docker resources"
+        );
+
+        tokio::select! {
+            _ = self.prune() => (),
+            _ = wait_for_signal() => {
+                event!(Level::WARN, "received repeated termination signal, skipping cleanup");
+            }
+        }
+
+        std::process::exit(code);
+    }
+
+    /// Prune all live resources in accordance with the prune strategy.
+    ///
+    /// An interrupted test is considered a failed test.
+    async fn prune(&self) {
+        let tracked = self.with_tracked(|t| t.clone());
+
+        let client = match connect_with_local_or_tls_defaults() {
+            Ok(c) => c,
+            Err(e) => {
+                event!(Level::ERROR, "unable to cleanup docker resources: {}", e);
+                return;
+            }
+        };
+
+        match env_prune_strategy() {
+            PruneStrategy::RunningRegardless | PruneStrategy::RunningOnFailure => {
+                event!(
+                    Level::DEBUG,
+                    "Leaving all containers running due to interrupted test"
+                );
+            }
+            PruneStrategy::StopOnFailure => {
+                unpause_containers(&client, &tracked.containers).await;
+                stop_containers(&client, &tracked.containers).await;
+                remove_networks(&client, &tracked.networks).await;
+            }
+            PruneStrategy::RemoveRegardless => {
+                // Volumes have to be removed after the containers, see `Runner::teardown`.
+                unpause_containers(&client, &tracked.containers).await;
+                remove_containers(&client, &tracked.containers).await;
+                remove_networks(&client, &tracked.networks).await;
+                remove_volumes(&client, &tracked.volumes).await;
+            }
+        }
+    }
+}
+
+/// Resolve the exit code for the first termination signal received.
+#[cfg(unix)]
+async fn wait_for_signal() -> i32 {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut terminate = match signal(SignalKind::terminate()) {
+        Ok(s) => s,
+        Err(e) => {
+            event!(Level::WARN, "unable to listen for SIGTERM: {}", e);
+            return wait_for_ctrl_c().await;
+        }
+    };
+
+    tokio::select! {
+        code = wait_for_ctrl_c() => code,
+        _ = terminate.recv() => 143,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> i32 {
+    wait_for_ctrl_c().await
+}
+
+async fn wait_for_ctrl_c() -> i32 {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        event!(Level::WARN, "unable to listen for SIGINT: {}", e);
+        futures::future::pending::<()>().await;
+    }
+    130
+}
+
+/// Paused containers can neither be stopped nor removed.
+async fn unpause_containers(client: &Docker, containers: &HashSet<String>) {
+    join_all(containers.iter().map(|id| unpause_if_paused(client, id))).await;
+}
+
+async fn stop_containers(client: &Docker, containers: &HashSet<String>) {
+    join_all(
+        containers
+            .iter()
+            .map(|id| client.stop_container(id, None::<StopContainerOptions>)),
+    )
+    .await;
+}
+
+async fn remove_containers(client: &Docker, containers: &HashSet<String>) {
+    let futures = containers.iter().map(|id| {
+        event!(Level::INFO, "removing container: {}", id);
+        let options = Some(RemoveContainerOptions {
+            force: true,
+            v: true,
+            ..Default::default()
+        });
+        client.remove_container(id, options)
+    });
+    join_all(futures).await;
+}
+
+/// Any container still attached to the network, such as static containers or the container
+/// dockertest itself executes within, must be disconnected prior to removing the network.
+async fn remove_networks(client: &Docker, networks: &HashSet<String>) {
+    for network in networks.iter() {
+        let attached = match client
+            .inspect_network(network, None::<InspectNetworkOptions<String>>)
+            .await
+        {
+            Ok(details) => details.containers.unwrap_or_default(),
+            Err(e) => {
+                event!(
+                    Level::ERROR,
+                    "unable to inspect docker network `{}`: {}",
+                    network,
+                    e
+                );
+                continue;
+            }
+        };
+
+        for container in attached.keys() {
+            let opts = DisconnectNetworkOptions::<&str> {
+                container,
+                force: true,
+            };
+            if let Err(e) = client.disconnect_network(network, opts).await {
+                event!(
+                    Level::ERROR,
+                    "unable to disconnect container from network `{}`: {}",
+                    network,
+                    e
+                );
+            }
+        }
+
+        if let Err(e) = client.remove_network(network).await {
+            event!(
+                Level::ERROR,
+                "unable to remove docker network `{}`: {}",
+                network,
+                e
+            );
+        }
+    }
+}
+
+async fn remove_volumes(client: &Docker, volumes: &HashSet<String>) {
+    join_all(volumes.iter().map(|v| {
+        event!(Level::INFO, "removing named volume: {:?}", &v);
+        let options = Some(RemoveVolumeOptions { force: true });
+        client.remove_volume(v, options)
+    }))
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::Resources;
+
+    // Registered resources are tracked until released.
+    #[test]
+    fn test_register_and_release() {
+        let resources = Resources::default();
+        let volumes = vec!["volume-a".to_string(), "volume-b".to_string()];
+
+        resources.register_container("container");
+        resources.register_network("network");
+        resources.register_volumes(&volumes);
+
+        let tracked = resources.with_tracked(|t| t.clone());
+        assert!(!tracked.is_empty());
+        assert!(tracked.containers.contains("container"));
+        assert!(tracked.networks.contains("network"));
+        assert_eq!(tracked.volumes.len(), 2, "volumes were not registered");
+
+        resources.release_container("container");
+        resources.release_network("network");
+        resources.release_volumes(&volumes);
+
+        let tracked = resources.with_tracked(|t| t.clone());
+        assert_eq!(
+            tracked,
+            Default::default(),
+            "released resources are still tracked"
+        );
+    }
+}
@@ -1,8 +1,12 @@
 //! The main library structures.
 
+use crate::composition::Composition;
 use crate::container::RunningContainer;
 use crate::dockertest::Network;
 use crate::engine::{bootstrap, Debris, Engine, Orbiting};
+use crate::partition::Partitions;
+use crate::report::{Outcome, Report};
+use crate::resources::RESOURCES;
 use crate::static_container::SCOPED_NETWORKS;
 use crate::utils::{connect_with_local_or_tls_defaults, generate_random_string};
 use crate::{DockerTest, DockerTestError};
@@ -59,6 +63,10 @@ pub(crate) struct Runner {
     /// suffixed with this ID.
     /// This applies to resouces such as docker network names and named volumes.
     pub(crate) id: String,
+    /// The report of this test run, written once the test completes if enabled.
+    report: Report,
+    /// Containers disconnected or partitioned by the test body, restored at teardown.
+    partitions: Partitions,
 }
 
 /// The test body parameter provided in the [DockerTest::run] argument closure.
@@ -70,10 +78,19 @@ pub struct DockerOperations {
     /// We _really_ wish to use a reference somehow here, but cannot easily do so due to
     /// lifetime conflicts. We may want to revisit this architecture decision in the future.
     engine: Engine<Orbiting>,
+    /// Containers disconnected or partitioned through this `DockerOperations`.
+    partitions: Partitions,
+}
+
+/// The outcome of a test body that was invoked.
+enum BodyOutcome {
+    Passed,
+    Panicked(Box<dyn Any + Send + 'static>),
+    Cancelled,
 }
 
 /// The prune strategy for teardown of containers.
-enum PruneStrategy {
+pub(crate) enum PruneStrategy {
     /// Always leave the container running
     RunningRegardless,
     /// Do not perform any action if the test failed.
@@ -99,6 +116,14 @@ impl DockerOperations {
         })
     }
 
+    /// Non-panicking lookup of the `RunningContainer` identified by each handle.
+    fn try_handles<'a>(
+        &'a self,
+        handles: &[&'a str],
+    ) -> Result<Vec<&'a RunningContainer>, DockerTestError> {
+        handles.iter().map(|h| self.try_handle(h)).collect()
+    }
+
     /// Retrieve the `RunningContainer` identified by this handle.
     ///
     /// A container is identified within dockertest by its assigned or derived handler.
@@ -120,6 +145,42 @@ impl DockerOperations {
         }
     }
 
+    /// Disconnect the container identified by this handle from the test network, e.g., to
+    /// observe how the remaining containers handle its absence.
+    ///
+    /// The container is unreachable by other containers until it is reconnected through
+    /// [DockerOperations::reconnect], which happens at teardown at the latest.
+    /// Static containers may be shared with other tests, and thus refuse this operation.
+    pub async fn disconnect(&self, handle: &str) -> Result<(), DockerTestError> {
+        let container = self.try_handle(handle)?;
+        self.partitions.disconnect(container).await
+    }
+
+    /// Reconnect the container identified by this handle to the test network, after it was
+    /// disconnected or partitioned.
+    ///
+    /// Does nothing if the container is connected to the test network.
+    pub async fn reconnect(&self, handle: &str) -> Result<(), DockerTestError> {
+        let container = self.try_handle(handle)?;
+        self.partitions.reconnect(container).await
+    }
+
+    /// Split the containers identified by the two groups of handles onto separate temporary
+    /// networks, such that containers within a group can reach each other, but not the
+    /// containers of the other group.
+    ///
+    /// Each container is disconnected from the test network, and is restored by
+    /// [DockerOperations::reconnect], or at teardown at the latest.
+    /// Containers are reachable by their container name within their partition, along with
+    /// the aliases they had on the test network. While partitioned, [RunningContainer::ip]
+    /// reports the address of the container on its partition.
+    pub async fn partition(&self, left: &[&str], right: &[&str]) -> Result<(), DockerTestError> {
+        let left = self.try_handles(left)?;
+        let right = self.try_handles(right)?;
+
+        self.partitions.partition(&[&left, &right]).await
+    }
+
     /// Indicate that this test failed with the accompanied message.
     pub fn failure(&self, msg: &str) {
         event!(Level::ERROR, "test failure: {}", msg);
@@ -142,6 +203,9 @@ impl Runner {
         let client = connect_with_local_or_tls_defaults()?;
         let id = generate_random_string(20);
 
+        // Ensure we cleanup after ourselves if the test process is interrupted.
+        RESOURCES.install_signal_handler();
+
         let network = match &config.network {
             Network::External(n) => n.clone(),
             Network::Isolated => format!("dockertest-rs-{}", id),
@@ -162,7 +226,9 @@ impl Runner {
         Ok(Runner {
             client,
             named_volumes: Vec::new(),
+            partitions: Partitions::new(&network, &id, own_container_id()),
             network,
+            report: Report::new(&id, &config.namespace),
             id,
             config,
         })
@@ -174,6 +240,23 @@ impl Runner {
         T: FnOnce(DockerOperations) -> Fut,
         Fut: Future<Output = ()> + Send + 'static,
     {
+        let result = self.run_test(test).await;
+        self.finish_report(&result);
+
+        match result? {
+            BodyOutcome::Passed => Ok(()),
+            BodyOutcome::Panicked(panic) => panic::resume_unwind(panic),
+            BodyOutcome::Cancelled => panic!("test future cancelled"),
+        }
+    }
+
+    async fn run_test<T, Fut>(&mut self, test: T) -> Result<BodyOutcome, DockerTestError>
+    where
+        T: FnOnce(DockerOperations) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.report.transition("bootstrapping");
+
         // If we are inside a container, we need to retrieve our container ID.
         self.check_if_inside_container();
 
@@ -186,7 +269,9 @@ impl Runner {
         engine.resolve_final_container_name(&self.config.namespace);
 
         let mut engine = engine.fuel();
+        self.report.transition("fueling");
         engine.resolve_inject_container_name_env()?;
+        engine.resolve_dependencies()?;
         engine
             .pull_images(&self.client, &self.config.default_source)
             .await?;
@@ -194,13 +279,14 @@ impl Runner {
         self.resolve_network().await?;
 
         // Create PendingContainers from the Compositions
+        self.report.transition("igniting");
         let engine = match engine
             .ignite(&self.client, &self.network, &self.config.network)
             .await
         {
             Ok(e) => e,
             Err(engine) => {
-                let mut creation_failures = engine.creation_failures();
+                let creation_failures = engine.creation_failures();
                 let total = creation_failures.len();
                 creation_failures.iter().enumerate().for_each(|(i, e)| {
                     trace!("container {} of {} creation failures: {}", i + 1, total, e);
@@ -214,17 +300,17 @@ impl Runner {
                 }
                 self.teardown(engine, false).await;
 
-                // QUESTION: What is the best option for us to propagate multiple errors?
-                return Err(creation_failures
-                    .pop()
-                    .expect("dockertest bug: cleanup path expected container creation error"));
+                return Err(DockerTestError::ContainerFailures(creation_failures));
             }
         };
 
         // Ensure we drive all the waitfor conditions to completion when we start the containers
-        let mut engine = match engine.orbiting().await {
-            Ok(e) => e,
-            Err((engine, e)) => {
+        let mut engine = match engine.orbiting(self.config.startup_timeout).await {
+            Ok(e) => {
+                self.report.transition("orbiting");
+                e
+            }
+            Err((engine, failures)) => {
                 // Teardown everything on error
                 let engine = engine.decommission();
                 if let Err(errors) = engine.handle_startup_logs().await {
@@ -234,7 +320,7 @@ impl Runner {
                 }
                 self.teardown(engine, false).await;
 
-                return Err(e);
+                return Err(DockerTestError::ContainerFailures(failures));
             }
         };
 
@@ -251,7 +337,7 @@ impl Runner {
         };
 
         // Run container inspection to get up-to-date runtime information
-        if let Err(mut errors) = engine.inspect(&self.client, &network_name).await {
+        if let Err(errors) = engine.inspect(&network_name).await {
             let total = errors.len();
             errors.iter().enumerate().for_each(|(i, e)| {
                 trace!("container {} of {} inspect failures: {}", i + 1, total, e);
@@ -261,52 +347,110 @@ impl Runner {
             let engine = engine.decommission();
             self.teardown(engine, false).await;
 
-            // QUESTION: What is the best option for us to propagate multiple errors?
-            return Err(errors
-                .pop()
-                .expect("dockertest bug: cleanup path expected container inspect error"));
+            return Err(DockerTestError::ContainerFailures(errors));
         };
 
+        // Run the user provided hooks now that the containers are ready and inspected
+        if let Err(failures) = engine.run_on_ready().await {
+            // Teardown everything on error
+            let engine = engine.decommission();
+            if let Err(errors) = engine.handle_startup_logs().await {
+                for err in errors {
+                    error!("{err}");
+                }
+            }
+            self.teardown(engine, false).await;
+
+            return Err(DockerTestError::ContainerFailures(failures));
+        }
+
         // We are ready to invoke the test body now
         let ops = DockerOperations {
             engine: engine.clone(),
+            partitions: self.partitions.clone(),
         };
 
         // Run test body
-        let result: Result<(), Option<Box<dyn Any + Send + 'static>>> =
-            match tokio::spawn(test(ops)).await {
-                Ok(_) => {
-                    event!(Level::DEBUG, "test body success");
-                    Ok(())
+        self.report.transition("test_body");
+        let mut body = tokio::spawn(test(ops));
+        let mut timed_out = None;
+        let joined = match self.config.test_timeout {
+            None => Some(body.await),
+            Some(timeout) => match tokio::time::timeout(timeout, &mut body).await {
+                Ok(joined) => Some(joined),
+                Err(_) => {
+                    event!(Level::ERROR, "test body timed out after {:?}", timeout);
+                    body.abort();
+                    timed_out = Some(timeout);
+                    None
                 }
-                Err(e) => {
-                    // Test failed
-                    event!(
-                        Level::DEBUG,
-                        "test body failed (cancelled: {}, panicked: {})",
-                        e.is_cancelled(),
-                        e.is_panic()
-                    );
-                    Err(e.try_into_panic().ok())
+            },
+        };
+
+        let outcome = match joined {
+            Some(Ok(_)) => {
+                event!(Level::DEBUG, "test body success");
+                BodyOutcome::Passed
+            }
+            Some(Err(e)) => {
+                // Test failed
+                event!(
+                    Level::DEBUG,
+                    "test body failed (cancelled: {}, panicked: {})",
+                    e.is_cancelled(),
+                    e.is_panic()
+                );
+                match e.try_into_panic() {
+                    Ok(panic) => BodyOutcome::Panicked(panic),
+                    Err(_) => BodyOutcome::Cancelled,
                 }
-            };
+            }
+            None => BodyOutcome::Cancelled,
+        };
+        let test_failed = !matches!(outcome, BodyOutcome::Passed);
 
+        let running = engine.running_handles();
         let engine = engine.decommission();
-        if let Err(errors) = engine.handle_logs(result.is_err()).await {
+        if let Err(errors) = engine.handle_logs(test_failed).await {
             for err in errors {
                 error!("{err}");
             }
         }
-        self.teardown(engine, result.is_err()).await;
+        self.teardown(engine, test_failed).await;
 
-        if let Err(option) = result {
-            match option {
-                Some(panic) => panic::resume_unwind(panic),
-                None => panic!("test future cancelled"),
-            }
+        if let Some(timeout) = timed_out {
+            return Err(DockerTestError::TestTimeout {
+                timeout,
+                containers: running,
+            });
         }
 
-        Ok(())
+        Ok(outcome)
+    }
+
+    /// Write the run report, if enabled.
+    fn finish_report(&mut self, result: &Result<BodyOutcome, DockerTestError>) {
+        let path = match self.report.destination(self.config.report_path.as_deref()) {
+            Some(p) => p,
+            None => return,
+        };
+
+        let (outcome, error) = match result {
+            Ok(BodyOutcome::Passed) => (Outcome::Passed, None),
+            Ok(BodyOutcome::Panicked(panic)) => (Outcome::Failed, panic_message(panic.as_ref())),
+            Ok(BodyOutcome::Cancelled) => {
+                (Outcome::Failed, Some("test future cancelled".to_string()))
+            }
+            Err(e @ DockerTestError::TestTimeout { .. }) => {
+                (Outcome::TimedOut, Some(e.to_string()))
+            }
+            Err(e) => (Outcome::SetupFailed, Some(e.to_string())),
+        };
+
+        self.report.outcome = Some(outcome);
+        self.report.error = error;
+        self.report.transition("completed");
+        self.report.write(&path);
     }
 
     /// Checks if we are inside a container, and if so sets our container ID.
@@ -338,13 +482,24 @@ impl Runner {
                     &self.network,
                     self.config.container_id.as_deref(),
                 )
-                .await
+                .await?;
+                RESOURCES.register_network(&self.network);
+                Ok(())
             }
         }
     }
 
     /// Teardown everything this test created, in accordance with the prune strategy.
-    async fn teardown(&self, engine: Engine<Debris>, test_failed: bool) {
+    async fn teardown(&mut self, engine: Engine<Debris>, test_failed: bool) {
+        self.report.transition("teardown");
+        self.report.containers = engine.container_reports();
+
+        // The prune strategy has been applied to our resources once teardown completes,
+        // they should no longer be handled if the test process is interrupted.
+        let containers = engine.managed_container_ids();
+        let mut errors = engine.unpause_containers(&self.client).await;
+        errors.extend(self.partitions.restore(&self.client).await);
+
         // Ensure we cleanup static container regardless of prune strategy
         engine
             .disconnect_static_containers(&self.client, &self.network, &self.config.network)
@@ -368,8 +523,8 @@ impl Runner {
             // We only stop, and do not remove, if test failed and our strategy
             // tells us to do so.
             PruneStrategy::StopOnFailure if test_failed => {
-                engine.stop_containers(&self.client).await;
-                self.teardown_network().await;
+                errors.extend(engine.stop_containers(&self.client).await);
+                errors.extend(self.teardown_network().await);
             }
 
             // Catch all to remove everything.
@@ -383,16 +538,28 @@ impl Runner {
                 // We therefore run the container remove futures to completion before trying to remove
                 // volumes. We will not be able to remove volumes if the associated container was not
                 // removed successfully.
-                engine.remove_containers(&self.client).await;
-                self.teardown_network().await;
+                errors.extend(engine.remove_containers(&self.client).await);
+                errors.extend(self.teardown_network().await);
 
-                self.remove_volumes().await;
+                errors.extend(self.remove_volumes().await);
             }
         }
+
+        self.report
+            .teardown_errors
+            .extend(errors.iter().map(|e| e.to_string()));
+
+        for id in containers.iter() {
+            RESOURCES.release_container(id);
+        }
+        if let Network::Isolated = self.config.network {
+            RESOURCES.release_network(&self.network);
+        }
+        RESOURCES.release_volumes(&self.named_volumes);
     }
 
-    async fn remove_volumes(&self) {
-        join_all(
+    async fn remove_volumes(&self) -> Vec<DockerTestError> {
+        let results = join_all(
             self.named_volumes
                 .iter()
                 .map(|v| {
@@ -403,40 +570,23 @@ impl Runner {
                 .collect::<Vec<_>>(),
         )
         .await;
+
+        self.named_volumes
+            .iter()
+            .zip(results)
+            .filter_map(|(v, result)| {
+                result.err().map(|e| {
+                    DockerTestError::Teardown(format!("failed to remove volume `{}`: {}", v, e))
+                })
+            })
+            .collect()
     }
 
     // Determines the final name for all named volumes, and modifies the Compositions accordingly.
-    // Named volumes will have the following form: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID:PATH_IN_CONTAINER".
     async fn resolve_named_volumes(&mut self) -> Result<(), DockerTestError> {
-        // Maps the original volume name to the suffixed ones
-        // Key: "USER_PROVIDED_VOLUME_NAME"
-        // Value: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID"
-        let mut volume_name_map: HashMap<String, String> = HashMap::new();
-
-        let suffix = self.id.clone();
-
-        // Add the dockertest ID as a suffix to all named volume names.
-        self.config.compositions.iter_mut().for_each(|c| {
-            // Includes path aswell: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID:PATH_IN_CONTAINER"
-            let mut volume_names_with_path: Vec<String> = Vec::new();
-
-            c.named_volumes.iter().for_each(|(id, path)| {
-                if let Some(suffixed_name) = volume_name_map.get(id) {
-                    volume_names_with_path.push(format!("{}:{}", &suffixed_name, &path));
-                } else {
-                    let volume_name_with_path = format!("{}-{}:{}", id, &suffix, path);
-                    volume_names_with_path.push(volume_name_with_path);
-
-                    let suffixed_volume_name = format!("{}-{}", id, &suffix);
-                    volume_name_map.insert(id.to_string(), suffixed_volume_name);
-                }
-            });
-
-            c.final_named_volume_names = volume_names_with_path;
-        });
-
         // Add all the suffixed volumes names to dockertest such that we can clean them up later.
-        self.named_volumes = volume_name_map.drain().map(|(_k, v)| v).collect();
+        self.named_volumes = suffix_named_volumes(&mut self.config.compositions, &self.id);
+        RESOURCES.register_volumes(&self.named_volumes);
 
         event!(
             Level::DEBUG,
@@ -447,29 +597,67 @@ impl Runner {
         Ok(())
     }
 
-    async fn teardown_network(&self) {
+    async fn teardown_network(&self) -> Option<DockerTestError> {
         match self.config.network {
             // The singular network should never be deleted
-            Network::Singular => (),
-            Network::External(_) => (),
-            Network::Isolated => {
-                delete_network(
-                    &self.client,
-                    &self.network,
-                    self.config.container_id.as_deref(),
-                )
-                .await
-            }
+            Network::Singular => None,
+            Network::External(_) => None,
+            Network::Isolated => delete_network(
+                &self.client,
+                &self.network,
+                self.config.container_id.as_deref(),
+            )
+            .await
+            .err(),
         }
     }
 }
 
-fn own_container_id() -> Option<String> {
+/// Extract the message of a panic payload, if it is a string.
+fn panic_message(panic: &(dyn Any + Send)) -> Option<String> {
+    panic
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+}
+
+// Suffix all named volumes with the dockertest ID, and modifies the Compositions accordingly.
+// Named volumes will have the following form: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID:PATH_IN_CONTAINER".
+// Returns the suffixed names of all named volumes.
+pub(crate) fn suffix_named_volumes(compositions: &mut [Composition], suffix: &str) -> Vec<String> {
+    // Maps the original volume name to the suffixed ones
+    // Key: "USER_PROVIDED_VOLUME_NAME"
+    // Value: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID"
+    let mut volume_name_map: HashMap<String, String> = HashMap::new();
+
+    compositions.iter_mut().for_each(|c| {
+        // Includes path aswell: "USER_PROVIDED_VOLUME_NAME-DOCKERTEST_ID:PATH_IN_CONTAINER"
+        let mut volume_names_with_path: Vec<String> = Vec::new();
+
+        c.named_volumes.iter().for_each(|(id, path)| {
+            if let Some(suffixed_name) = volume_name_map.get(id) {
+                volume_names_with_path.push(format!("{}:{}", &suffixed_name, &path));
+            } else {
+                let volume_name_with_path = format!("{}-{}:{}", id, suffix, path);
+                volume_names_with_path.push(volume_name_with_path);
+
+                let suffixed_volume_name = format!("{}-{}", id, suffix);
+                volume_name_map.insert(id.to_string(), suffixed_volume_name);
+            }
+        });
+
+        c.final_named_volume_names = volume_names_with_path;
+    });
+
+    volume_name_map.drain().map(|(_k, v)| v).collect()
+}
+
+pub(crate) fn own_container_id() -> Option<String> {
     std::env::var("DOCKERTEST_CONTAINER_ID_INJECT_TO_NETWORK").ok()
 }
 
 /// Resolve the current prune strategy, provided by the environment.
-fn env_prune_strategy() -> PruneStrategy {
+pub(crate) fn env_prune_strategy() -> PruneStrategy {
     match std::env::var_os("DOCKERTEST_PRUNE") {
         Some(val) => match val.to_string_lossy().to_lowercase().as_str() {
             "stop_on_failure" => PruneStrategy::StopOnFailure,
@@ -492,7 +680,7 @@ pub(crate) async fn delete_network(
     client: &Docker,
     network_name: &str,
     self_container: Option<&str>,
-) {
+) -> Result<(), DockerTestError> {
     if let Some(id) = self_container {
         let opts = DisconnectNetworkOptions::<&str> {
             container: id,
@@ -507,14 +695,18 @@ pub(crate) async fn delete_network(
         }
     }
 
-    if let Err(e) = client.remove_network(network_name).await {
+    client.remove_network(network_name).await.map_err(|e| {
         event!(
             Level::ERROR,
             "unable to remove docker network `{}`: {}",
             network_name,
             e
         );
-    }
+        DockerTestError::Teardown(format!(
+            "failed to remove network `{}`: {}",
+            network_name, e
+        ))
+    })
 }
 
 pub(crate) async fn create_network(
@@ -1,11 +1,15 @@
 //! The various mechanism available to specify a container to be part of the test.
 
 use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use futures::future::Future;
 
 use crate::{
-    composition::{Composition, StaticManagementPolicy},
+    composition::{Composition, OnReady, StaticManagementPolicy},
     waitfor::WaitFor,
-    Image, LogOptions, StartPolicy,
+    HealthCheck, Image, LogOptions, RetryPolicy, RunningContainer, StartPolicy,
 };
 
 mod private {
@@ -34,7 +38,8 @@ macro_rules! impl_specify_container {
             /// Sets the [StartPolicy] when having to create and spin up this container.
             ///
             /// The start policy is needed to determine the order of containers to start in order
-            /// to successfully start dependant containers.
+            /// to successfully start dependant containers. Prefer declaring explicit dependencies
+            /// through [depends_on](Self::depends_on).
             ///
             /// If not specified, [StartPolicy::Relaxed] is the default policy.
             pub fn set_start_policy(self, start_policy: StartPolicy) -> Self {
@@ -43,6 +48,20 @@ macro_rules! impl_specify_container {
                 }
             }
 
+            /// Declare that this container depends on the container identified by `handle`.
+            ///
+            /// This container will be started as soon as the [WaitFor] condition of each of
+            /// its dependencies has been fulfilled. Containers without dependencies between them
+            /// are started concurrently.
+            ///
+            /// Unknown handles and dependency cycles are rejected before any container is created.
+            ///
+            /// [WaitFor]: crate::waitfor::WaitFor
+            pub fn depends_on<T: ToString>(&mut self, handle: T) -> &mut Self {
+                self.composition.depends_on(handle);
+                self
+            }
+
             /// Assign the full set of environment variables into the [RunningContainer].
             ///
             /// Each key in the map should be the environmental variable name
@@ -182,6 +201,63 @@ macro_rules! impl_specify_container {
                 }
             }
 
+            /// Set the deadline for this container to become ready once it has been started.
+            ///
+            /// Once the deadline expires, the [WaitFor] of this container is cancelled and the
+            /// test fails with a startup error. By default, there is no deadline.
+            ///
+            /// [WaitFor]: crate::waitfor::WaitFor
+            pub fn set_startup_timeout(self, timeout: Duration) -> Self {
+                Self {
+                    composition: self.composition.with_startup_timeout(timeout),
+                }
+            }
+
+            /// Retry starting this container in accordance with the [RetryPolicy] whenever
+            /// its [WaitFor] condition fails.
+            ///
+            /// The failed container is removed and created anew for each attempt. Should every
+            /// attempt fail, the startup error lists the failure of each attempt.
+            /// By default, a container is not retried.
+            ///
+            /// [WaitFor]: crate::waitfor::WaitFor
+            pub fn set_retry_policy(self, retry_policy: RetryPolicy) -> Self {
+                Self {
+                    composition: self.composition.with_retry_policy(retry_policy),
+                }
+            }
+
+            /// Register an async hook invoked with the [RunningContainer] once it is ready.
+            ///
+            /// The hook runs after the container has been inspected, and before the test body is
+            /// invoked, making it suitable for seeding data or registering schemas.
+            /// For static containers, the hook is only invoked by the first test to use the
+            /// container. A hook returning an error fails the test with a startup error.
+            ///
+            /// [RunningContainer]: crate::container::RunningContainer
+            pub fn set_on_ready<F, Fut, E>(self, hook: F) -> Self
+            where
+                F: Fn(RunningContainer) -> Fut + Send + Sync + 'static,
+                Fut: Future<Output = Result<(), E>> + Send + 'static,
+                E: Display,
+            {
+                Self {
+                    composition: self.composition.with_on_ready(OnReady::new(hook)),
+                }
+            }
+
+            /// Set the [HealthCheck] run by the docker daemon within this container, overriding
+            /// the `HEALTHCHECK` of its image.
+            ///
+            /// Combine with [HealthyWait] to await the container becoming healthy.
+            ///
+            /// [HealthyWait]: crate::waitfor::HealthyWait
+            pub fn set_healthcheck(self, healthcheck: HealthCheck) -> Self {
+                Self {
+                    composition: self.composition.with_healthcheck(healthcheck),
+                }
+            }
+
             /// Specify how to handle logging from the container.
             ///
             /// If not specified, [LogAction::Forward], [LogPolicy::OnError] and
@@ -241,9 +317,12 @@ macro_rules! impl_specify_container {
             /// variables for connection details, and thus the DNS resolving capabilities within
             /// docker will map the container name into the correct IP address.
             ///
-            /// To correctly use this feature, the `StartPolicy` between the dependant containers
-            /// must be configured such that these connections can successfully be established.
-            /// Dockertest will not make any attempt to verify the integrity of these dependencies.
+            /// To correctly use this feature, the dependencies between the containers must be
+            /// declared through [depends_on] such that these connections can successfully be
+            /// established. Dockertest will not make any attempt to verify the integrity of
+            /// these dependencies.
+            ///
+            /// [depends_on]: Self::depends_on
             // TODO: naming
             // TODO: Refactor to use some reference mechanism
             pub fn inject_container_name<T: ToString, E: ToString>(
@@ -33,6 +33,7 @@ enum DynamicStatus {
     /// The container is in a running state and was not running prior to test invocation
     Running(RunningContainer, PendingContainer),
     Pending(PendingContainer),
+    #[allow(dead_code)]
     Failed(DockerTestError, Option<String>),
 }
 
@@ -157,13 +158,15 @@ impl DynamicContainers {
 
         if let Some(existing) = map.get_mut(&container.name) {
             match &existing.status {
-                DynamicStatus::Running(r, _) | DynamicStatus::RunningPrior(r) => Ok(r.clone()),
+                DynamicStatus::Running(r, _) | DynamicStatus::RunningPrior(r) => {
+                    Ok(r.clone_unshared())
+                }
                 DynamicStatus::Pending(p) => {
                     let cloned = p.clone();
                     let running = cloned.start_internal().await;
                     match running {
                         Ok(r) => {
-                            existing.status = DynamicStatus::Running(r.clone(), p.clone());
+                            existing.status = DynamicStatus::Running(r.clone_unshared(), p.clone());
                             Ok(r)
                         }
                         Err(e) => {
@@ -191,7 +194,7 @@ impl DynamicContainers {
                 DynamicStatus::Running(_, _)
                 | DynamicStatus::Pending(_)
                 | DynamicStatus::Failed(_, _) => None,
-                DynamicStatus::RunningPrior(c) => Some(c.clone()),
+                DynamicStatus::RunningPrior(c) => Some(c.clone_unshared()),
             })
             .collect()
     }
@@ -73,7 +73,12 @@ impl ExternalContainers {
     }
 
     pub async fn containers(&self) -> Vec<RunningContainer> {
-        self.inner.read().await.values().cloned().collect()
+        self.inner
+            .read()
+            .await
+            .values()
+            .map(RunningContainer::clone_unshared)
+            .collect()
     }
 
     pub async fn disconnect(
@@ -8,7 +8,8 @@ use bollard::Docker;
 
 use super::{add_to_network, disconnect_container, remove_container};
 use crate::{
-    composition::Composition, DockerTestError, Network, PendingContainer, RunningContainer,
+    composition::Composition, resources::RESOURCES, DockerTestError, Network, PendingContainer,
+    RunningContainer,
 };
 
 #[derive(Default)]
@@ -85,13 +86,13 @@ impl InternalContainers {
         if let Some(c) = map.get_mut(&container.name) {
             match &c.status {
                 InternalStatus::Failed(e, _) => Err(e.clone()),
-                InternalStatus::Running(r, _) => Ok(r.clone()),
+                InternalStatus::Running(r, _) => Ok(r.clone_unshared()),
                 InternalStatus::Pending(p) => {
                     let cloned = p.clone();
                     let running = cloned.start_internal().await;
                     match running {
                         Ok(r) => {
-                            c.status = InternalStatus::Running(r.clone(), p.clone());
+                            c.status = InternalStatus::Running(r.clone_unshared(), p.clone());
                             Ok(r)
                         }
                         Err(e) => {
@@ -116,12 +117,20 @@ impl InternalContainers {
         }
     }
 
-    pub async fn cleanup(&self, client: &Docker, network: &str, to_cleanup: &HashSet<&str>) {
+    /// Returns the ids of the containers removed, as no other test is using them.
+    pub async fn cleanup(
+        &self,
+        client: &Docker,
+        network: &str,
+        to_cleanup: &HashSet<&str>,
+    ) -> Vec<String> {
         self.disconnect(client, network, to_cleanup).await;
         let to_remove = self.decrement_completion_counters(to_cleanup).await;
-        for to_cleanup in to_remove {
-            remove_container(&to_cleanup, client).await;
+        for to_cleanup in to_remove.iter() {
+            remove_container(to_cleanup, client).await;
+            RESOURCES.release_container(to_cleanup);
         }
+        to_remove
     }
 
     async fn create_internal_container_inner(
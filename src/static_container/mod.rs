@@ -1,6 +1,6 @@
 use crate::{
-    composition::{Composition, StaticManagementPolicy},
-    container::{CreatedContainer, HostPortMappings},
+    composition::{Composition, OnReady, StaticManagementPolicy},
+    container::CreatedContainer,
     DockerTestError, Network, PendingContainer, RunningContainer,
 };
 use dynamic::DynamicContainers;
@@ -12,7 +12,9 @@ use bollard::{
     network::DisconnectNetworkOptions, Docker,
 };
 use lazy_static::lazy_static;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Mutex;
 use tracing::{event, Level};
 
 mod dynamic;
@@ -43,6 +45,8 @@ pub struct StaticContainers {
     internal: InternalContainers,
     external: ExternalContainers,
     dynamic: DynamicContainers,
+    /// Whether the `on_ready` hook has completed successfully, per static container id.
+    ready: Mutex<HashMap<String, Arc<Mutex<bool>>>>,
 }
 
 impl StaticContainers {
@@ -110,6 +114,34 @@ impl StaticContainers {
         }
     }
 
+    /// Invoke the `on_ready` hook of a static container, unless it has already completed
+    /// successfully for this container.
+    ///
+    /// The lock of the container is held while the hook runs, such that concurrent tests
+    /// sharing the container await its completion rather than invoking it again.
+    pub async fn on_ready_once(
+        &self,
+        container: &RunningContainer,
+        hook: &OnReady,
+    ) -> Result<(), DockerTestError> {
+        let entry = self
+            .ready
+            .lock()
+            .await
+            .entry(container.id.clone())
+            .or_default()
+            .clone();
+
+        let mut ready = entry.lock().await;
+        if *ready {
+            return Ok(());
+        }
+
+        hook.call(container.clone()).await?;
+        *ready = true;
+        Ok(())
+    }
+
     pub async fn cleanup(
         &self,
         client: &Docker,
@@ -118,7 +150,14 @@ impl StaticContainers {
         to_cleanup: Vec<&str>,
     ) {
         let cleanup: HashSet<&str> = to_cleanup.into_iter().collect();
-        self.internal.cleanup(client, network, &cleanup).await;
+        let removed = self.internal.cleanup(client, network, &cleanup).await;
+
+        // A container created anew with the same name must invoke its `on_ready` hook again.
+        self.ready
+            .lock()
+            .await
+            .retain(|id, _| !removed.contains(id));
+
         self.dynamic
             .disconnect(client, network, network_mode, &cleanup)
             .await;
@@ -133,11 +172,6 @@ async fn add_to_network(
     network: &str,
     client: &Docker,
 ) -> Result<(), DockerTestError> {
-    let opts = bollard::network::ConnectNetworkOptions {
-        container: container_id,
-        endpoint_config: bollard::models::EndpointSettings::default(),
-    };
-
     event!(
         Level::DEBUG,
         "adding to network: {}, container: {}",
@@ -145,32 +179,54 @@ async fn add_to_network(
         container_id
     );
 
-    match client.connect_network(network, opts).await {
-        Ok(_) => Ok(()),
-        Err(e) => match e {
-            bollard::errors::Error::DockerResponseServerError {
-                status_code,
-                message: _,
-            } => {
-                // The container was already connected to the network which is what we wanted
-                // anyway
-                if status_code == 403 {
-                    Ok(())
-                } else {
-                    Err(DockerTestError::Startup(format!(
-                        "failed to add static container to dockertest network: {}",
-                        e
-                    )))
-                }
-            }
-            _ => Err(DockerTestError::Startup(format!(
+    connect_network(client, container_id, network, None)
+        .await
+        .map_err(|e| {
+            DockerTestError::Startup(format!(
                 "failed to add static container to dockertest network: {}",
                 e
-            ))),
+            ))
+        })
+}
+
+/// Connect the container to the network under the given aliases, succeeding if the container
+/// is already connected.
+pub(crate) async fn connect_network(
+    client: &Docker,
+    container_id: &str,
+    network: &str,
+    aliases: Option<Vec<String>>,
+) -> Result<(), bollard::errors::Error> {
+    let opts = bollard::network::ConnectNetworkOptions {
+        container: container_id,
+        endpoint_config: bollard::models::EndpointSettings {
+            aliases,
+            ..Default::default()
         },
+    };
+
+    match client.connect_network(network, opts).await {
+        // The container was already connected to the network which is what we wanted anyway
+        Err(bollard::errors::Error::DockerResponseServerError {
+            status_code: 403, ..
+        }) => Ok(()),
+        result => result,
     }
 }
 
+/// Forcefully disconnect the container from the network.
+pub(crate) async fn disconnect_network(
+    client: &Docker,
+    container_id: &str,
+    network: &str,
+) -> Result<(), bollard::errors::Error> {
+    let opts = DisconnectNetworkOptions::<&str> {
+        container: container_id,
+        force: true,
+    };
+    client.disconnect_network(network, opts).await
+}
+
 async fn remove_container(id: &str, client: &Docker) {
     let remove_opts = Some(RemoveContainerOptions {
         force: true,
@@ -182,11 +238,7 @@ async fn remove_container(id: &str, client: &Docker) {
     }
 }
 async fn disconnect_container(client: &Docker, container_id: &str, network: &str) {
-    let opts = DisconnectNetworkOptions::<&str> {
-        container: container_id,
-        force: true,
-    };
-    if let Err(e) = client.disconnect_network(network, opts).await {
+    if let Err(e) = disconnect_network(client, container_id, network).await {
         event!(
             Level::ERROR,
             "unable to remove dockertest-container from network: {}",
@@ -206,10 +258,10 @@ async fn running_container_from_composition(
             id,
             name: composition.container_name.clone(),
             handle: composition.container_name,
-            ip: std::net::Ipv4Addr::UNSPECIFIED,
-            ports: HostPortMappings::default(),
+            inspected: Default::default(),
             is_static: true,
             log_options: composition.log_options,
+            wait: None,
         })
     } else {
         Err(DockerTestError::Daemon(
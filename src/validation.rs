@@ -0,0 +1,172 @@
+//! Detect invalid configurations of a test, prior to interacting with the docker daemon.
+
+use crate::composition::StaticManagementPolicy;
+use crate::error::ValidationError;
+use crate::DockerTest;
+
+use std::collections::{BTreeMap, HashSet};
+
+/// Collect every problem with the configuration of the given [DockerTest].
+pub(crate) fn validate(config: &DockerTest) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    let mut handles = HashSet::new();
+    let mut duplicates = Vec::new();
+    for c in config.compositions.iter() {
+        let handle = c.handle();
+        if !handles.insert(handle.clone()) && !duplicates.contains(&handle) {
+            duplicates.push(handle);
+        }
+    }
+    errors.extend(
+        duplicates
+            .into_iter()
+            .map(|handle| ValidationError::DuplicateHandle { handle }),
+    );
+
+    // Host port -> the handle of the container for each binding.
+    let mut host_ports: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+    for c in config.compositions.iter() {
+        let handle = c.handle();
+
+        // The name of an external container references an existing container, and is thus
+        // not subject to our naming rules.
+        if c.static_management_policy() == &Some(StaticManagementPolicy::External) {
+            if handle.trim().is_empty() {
+                errors.push(ValidationError::MissingExternalName {
+                    handle: handle.clone(),
+                });
+            }
+        } else {
+            let mut named = c.clone();
+            named.configure_container_name(&config.namespace, "suffix");
+            if !is_valid_container_name(&named.container_name) {
+                errors.push(ValidationError::InvalidContainerName {
+                    handle: handle.clone(),
+                    name: named.container_name,
+                });
+            }
+        }
+
+        for (target, _) in c.inject_container_name_env.iter() {
+            if !handles.contains(target) {
+                errors.push(ValidationError::UnknownInjectHandle {
+                    handle: handle.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+
+        for target in c.dependencies.iter() {
+            if !handles.contains(target) {
+                errors.push(ValidationError::UnknownDependencyHandle {
+                    handle: handle.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+
+        for (_, host) in c.port.iter() {
+            host_ports.entry(host).or_default().push(handle.clone());
+        }
+    }
+
+    errors.extend(
+        host_ports
+            .into_iter()
+            .filter(|(_, handles)| handles.len() > 1)
+            .map(|(port, handles)| ValidationError::DuplicateHostPort {
+                port: port.to_string(),
+                handles,
+            }),
+    );
+
+    errors
+}
+
+/// The docker daemon only accepts container names matching `[a-zA-Z0-9][a-zA-Z0-9_.-]+`.
+fn is_valid_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphanumeric() => (),
+        _ => return false,
+    }
+
+    let rest = chars.as_str();
+    !rest.is_empty()
+        && rest
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ValidationError;
+    use crate::specification::{ExternalSpecification, TestBodySpecification};
+    use crate::{DockerTest, DockerTestError};
+
+    // Every problem is reported at once.
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let mut db = TestBodySpecification::with_repository("postgres").set_handle("db");
+        db.modify_port_map(5432, 15432);
+        let mut db2 = TestBodySpecification::with_repository("postgres").set_handle("db");
+        db2.modify_port_map(5433, 15432);
+        let mut api = TestBodySpecification::with_repository("localhost:5000/api");
+        api.inject_container_name("cache", "CACHE_HOST")
+            .depends_on("queue");
+
+        let mut test = DockerTest::new();
+        test.provide_container(db)
+            .provide_container(db2)
+            .provide_container(api)
+            .provide_container(ExternalSpecification::with_container_name(""));
+
+        let errors = match test.validate() {
+            Err(DockerTestError::Validation(errors)) => errors,
+            result => panic!("unexpected validation result: {:?}", result),
+        };
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::DuplicateHandle {
+                    handle: "db".to_string(),
+                },
+                ValidationError::InvalidContainerName {
+                    handle: "localhost:5000/api".to_string(),
+                    name: "dockertest-rs-localhost:5000_api-suffix".to_string(),
+                },
+                ValidationError::UnknownInjectHandle {
+                    handle: "localhost:5000/api".to_string(),
+                    target: "cache".to_string(),
+                },
+                ValidationError::UnknownDependencyHandle {
+                    handle: "localhost:5000/api".to_string(),
+                    target: "queue".to_string(),
+                },
+                ValidationError::MissingExternalName {
+                    handle: "".to_string(),
+                },
+                ValidationError::DuplicateHostPort {
+                    port: "15432".to_string(),
+                    handles: vec!["db".to_string(), "db".to_string()],
+                },
+            ]
+        );
+    }
+
+    // A valid configuration passes validation.
+    #[test]
+    fn test_validate_valid_configuration() {
+        let db = TestBodySpecification::with_repository("postgres").set_handle("db");
+        let mut api = TestBodySpecification::with_repository("org/api");
+        api.inject_container_name("db", "DB_HOST").depends_on("db");
+
+        let mut test = DockerTest::new();
+        test.provide_container(db).provide_container(api);
+
+        assert_eq!(test.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,360 @@
+//! `WaitFor` implementations composing other `WaitFor` conditions.
+
+use crate::container::{PendingContainer, RunningContainer};
+use crate::waitfor::{async_trait, WaitFor};
+use crate::DockerTestError;
+
+use futures::future::{try_join_all, Future, FutureExt};
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::time::Duration;
+
+/// The AllOf `WaitFor` implementation for containers.
+/// This variant will wait until every one of its conditions is fulfilled, checking them
+/// concurrently.
+///
+/// Fails as soon as any of the conditions fails.
+#[derive(Clone, Debug)]
+pub struct AllOf {
+    waits: Vec<Box<dyn WaitFor>>,
+    timeout: Option<Duration>,
+}
+
+/// The AnyOf `WaitFor` implementation for containers.
+/// This variant will wait until one of its conditions is fulfilled, checking them
+/// concurrently.
+///
+/// Fails once all of the conditions have failed.
+#[derive(Clone, Debug)]
+pub struct AnyOf {
+    waits: Vec<Box<dyn WaitFor>>,
+    timeout: Option<Duration>,
+}
+
+/// The Sequence `WaitFor` implementation for containers.
+/// This variant will wait until each of its conditions is fulfilled, one after the other in
+/// the given order.
+///
+/// Fails as soon as any of the conditions fails.
+#[derive(Clone, Debug)]
+pub struct Sequence {
+    waits: Vec<Box<dyn WaitFor>>,
+    timeout: Option<Duration>,
+}
+
+impl AllOf {
+    /// Creates an [AllOf] over the given conditions.
+    pub fn new(waits: Vec<Box<dyn WaitFor>>) -> AllOf {
+        AllOf {
+            waits,
+            timeout: None,
+        }
+    }
+
+    /// Sets the deadline for all conditions to be fulfilled. By default, there is no deadline.
+    pub fn with_timeout(self, timeout: Duration) -> AllOf {
+        AllOf {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
+impl AnyOf {
+    /// Creates an [AnyOf] over the given conditions.
+    pub fn new(waits: Vec<Box<dyn WaitFor>>) -> AnyOf {
+        AnyOf {
+            waits,
+            timeout: None,
+        }
+    }
+
+    /// Sets the deadline for any condition to be fulfilled. By default, there is no deadline.
+    pub fn with_timeout(self, timeout: Duration) -> AnyOf {
+        AnyOf {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
+impl Sequence {
+    /// Creates a [Sequence] over the given conditions, in order.
+    pub fn new(waits: Vec<Box<dyn WaitFor>>) -> Sequence {
+        Sequence {
+            waits,
+            timeout: None,
+        }
+    }
+
+    /// Sets the deadline for the whole sequence to be fulfilled. By default, there is no
+    /// deadline.
+    pub fn with_timeout(self, timeout: Duration) -> Sequence {
+        Sequence {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+}
+
+#[async_trait]
+impl WaitFor for AllOf {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        self.check_ready(&container).await?;
+        Ok(container.into())
+    }
+
+    async fn check_ready(&self, container: &PendingContainer) -> Result<(), DockerTestError> {
+        let checks = self.waits.iter().map(|w| w.check_ready(container));
+        within(
+            self.timeout,
+            "AllOf",
+            try_join_all(checks).map(|r| r.map(|_| ())),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl WaitFor for AnyOf {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        self.check_ready(&container).await?;
+        Ok(container.into())
+    }
+
+    async fn check_ready(&self, container: &PendingContainer) -> Result<(), DockerTestError> {
+        if self.waits.is_empty() {
+            return Err(DockerTestError::Startup(
+                "`AnyOf` without conditions can never be fulfilled".to_string(),
+            ));
+        }
+
+        let any = async {
+            let mut checks: FuturesUnordered<_> = self
+                .waits
+                .iter()
+                .map(|w| w.check_ready(container))
+                .collect();
+
+            // Collect the error of each failed condition, until one is fulfilled.
+            let mut errors = Vec::new();
+            while let Some(result) = checks.next().await {
+                match result {
+                    Ok(()) => return Ok(()),
+                    Err(e) => errors.push(e.to_string()),
+                }
+            }
+
+            Err(DockerTestError::Startup(format!(
+                "none of the conditions were fulfilled: {}",
+                errors.join("; ")
+            )))
+        };
+        within(self.timeout, "AnyOf", any).await
+    }
+}
+
+#[async_trait]
+impl WaitFor for Sequence {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        self.check_ready(&container).await?;
+        Ok(container.into())
+    }
+
+    async fn check_ready(&self, container: &PendingContainer) -> Result<(), DockerTestError> {
+        let sequence = async {
+            for wait in self.waits.iter() {
+                wait.check_ready(container).await?;
+            }
+            Ok(())
+        };
+        within(self.timeout, "Sequence", sequence).await
+    }
+}
+
+/// Bound the conditions by the deadline, if any.
+async fn within<F>(
+    timeout: Option<Duration>,
+    combinator: &str,
+    conditions: F,
+) -> Result<(), DockerTestError>
+where
+    F: Future<Output = Result<(), DockerTestError>>,
+{
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, conditions)
+            .await
+            .map_err(|_| {
+                DockerTestError::Startup(format!(
+                    "`{}` conditions not fulfilled within {:?}",
+                    combinator, timeout
+                ))
+            })?,
+        None => conditions.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::{PendingContainer, RunningContainer};
+    use crate::utils::connect_with_local_or_tls_defaults;
+    use crate::waitfor::{async_trait, AllOf, AnyOf, NoWait, Sequence, WaitFor};
+    use crate::{DockerTestError, StartPolicy};
+
+    use std::sync::{Arc, Mutex};
+    use tokio::time::Duration;
+
+    /// Records its name once ready, optionally failing instead.
+    #[derive(Clone, Debug)]
+    struct RecordWait {
+        name: &'static str,
+        delay: Duration,
+        fail: bool,
+        record: Record,
+    }
+
+    #[async_trait]
+    impl WaitFor for RecordWait {
+        async fn wait_for_ready(
+            &self,
+            container: PendingContainer,
+        ) -> Result<RunningContainer, DockerTestError> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(DockerTestError::Startup(format!("{} failed", self.name)));
+            }
+            self.record.lock().unwrap().push(self.name);
+            Ok(container.into())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct HangWait {}
+
+    #[async_trait]
+    impl WaitFor for HangWait {
+        async fn wait_for_ready(
+            &self,
+            _container: PendingContainer,
+        ) -> Result<RunningContainer, DockerTestError> {
+            futures::future::pending().await
+        }
+    }
+
+    fn pending_container() -> PendingContainer {
+        PendingContainer::new(
+            "name",
+            "id",
+            "handle",
+            StartPolicy::Relaxed,
+            Box::new(NoWait {}),
+            None,
+            None,
+            connect_with_local_or_tls_defaults().unwrap(),
+            None,
+            None,
+        )
+    }
+
+    type Record = Arc<Mutex<Vec<&'static str>>>;
+
+    /// Construct a `RecordWait` for each (name, delay in millis, fail) specification.
+    fn waits(specs: &[(&'static str, u64, bool)]) -> (Vec<Box<dyn WaitFor>>, Record) {
+        let record = Arc::new(Mutex::new(Vec::new()));
+        let waits = specs
+            .iter()
+            .map(|(name, delay, fail)| {
+                Box::new(RecordWait {
+                    name,
+                    delay: Duration::from_millis(*delay),
+                    fail: *fail,
+                    record: record.clone(),
+                }) as Box<dyn WaitFor>
+            })
+            .collect();
+        (waits, record)
+    }
+
+    #[tokio::test]
+    async fn test_all_of() {
+        let (all, record) = waits(&[("slow", 50, false), ("fast", 0, false)]);
+        assert!(AllOf::new(all)
+            .check_ready(&pending_container())
+            .await
+            .is_ok());
+        assert_eq!(*record.lock().unwrap(), vec!["fast", "slow"]);
+
+        let (all, _) = waits(&[("ok", 0, false), ("bad", 0, true)]);
+        assert_eq!(
+            AllOf::new(all).check_ready(&pending_container()).await,
+            Err(DockerTestError::Startup("bad failed".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_any_of() {
+        let (any, record) = waits(&[("bad", 0, true), ("ok", 10, false)]);
+        assert!(AnyOf::new(any)
+            .check_ready(&pending_container())
+            .await
+            .is_ok());
+        assert_eq!(*record.lock().unwrap(), vec!["ok"]);
+
+        let (any, _) = waits(&[("first", 0, true), ("second", 10, true)]);
+        assert_eq!(
+            AnyOf::new(any).check_ready(&pending_container()).await,
+            Err(DockerTestError::Startup(
+                "none of the conditions were fulfilled: startup condition not fulfilled \
+                 `first failed`; startup condition not fulfilled `second failed`"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sequence() {
+        let (sequence, record) = waits(&[("slow", 20, false), ("fast", 0, false)]);
+        assert!(Sequence::new(sequence)
+            .check_ready(&pending_container())
+            .await
+            .is_ok());
+        assert_eq!(*record.lock().unwrap(), vec!["slow", "fast"]);
+
+        let (sequence, record) = waits(&[("bad", 0, true), ("never", 0, false)]);
+        assert!(Sequence::new(sequence)
+            .check_ready(&pending_container())
+            .await
+            .is_err());
+        assert!(record.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_combinator_timeout() {
+        let (mut all, _) = waits(&[("ok", 0, false)]);
+        all.push(Box::new(HangWait {}));
+        let any: Vec<Box<dyn WaitFor>> = vec![Box::new(HangWait {})];
+
+        assert_eq!(
+            AllOf::new(all)
+                .with_timeout(Duration::from_millis(50))
+                .check_ready(&pending_container())
+                .await,
+            Err(DockerTestError::Startup(
+                "`AllOf` conditions not fulfilled within 50ms".to_string()
+            ))
+        );
+        assert!(AnyOf::new(any)
+            .with_timeout(Duration::from_millis(50))
+            .check_ready(&pending_container())
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! `WaitFor` implementation polling a command within the container.
+
+use crate::container::{exec, ExecOptions, PendingContainer, RunningContainer};
+use crate::waitfor::{async_trait, WaitFor};
+use crate::DockerTestError;
+
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{event, Level};
+
+/// The ExecWait `WaitFor` implementation for containers.
+/// This variant will repeatedly execute a command within the container until it exits with
+/// the expected exit code, e.g., `pg_isready`, `mysqladmin ping` or `redis-cli ping`.
+///
+/// Should the command not succeed in time, the error reports the output of the last completed
+/// attempt, along with the error of the last attempt that failed to execute the command.
+#[derive(Clone, Debug)]
+pub struct ExecWait {
+    /// The command to execute, where the first element is the executable and the remaining
+    /// elements its arguments.
+    pub cmd: Vec<String>,
+    /// The exit code signaling that the container is ready.
+    pub expected_exit_code: i64,
+    /// The duration between each attempt.
+    pub interval: Duration,
+    /// The duration to await the expected exit code before erroring out.
+    pub timeout: Duration,
+}
+
+#[async_trait]
+impl WaitFor for ExecWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        let deadline = Instant::now() + self.timeout;
+        let mut last = None;
+        let mut last_error = None;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let options = ExecOptions::new(self.cmd.clone()).with_timeout(remaining);
+
+            match exec::exec(&container.client, &container.handle, &container.id, options).await {
+                Ok(output) if output.exit_code == self.expected_exit_code => {
+                    return Ok(container.into())
+                }
+                Ok(output) => last = Some(Box::new(output)),
+                // The container may not be ready to execute commands yet.
+                Err(e) => {
+                    event!(Level::TRACE, "exec wait attempt failed: {}", e);
+                    last_error = Some(Box::new(e));
+                }
+            }
+
+            if Instant::now() + self.interval > deadline {
+                return Err(DockerTestError::ExecWaitTimeout {
+                    handle: container.handle.clone(),
+                    cmd: self.cmd.join(" "),
+                    expected_exit_code: self.expected_exit_code,
+                    timeout: self.timeout,
+                    last,
+                    last_error,
+                });
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! `WaitFor` implementation awaiting the healthcheck of a container.
+
+use crate::container::{HealthStatus, PendingContainer, RunningContainer};
+use crate::waitfor::{async_trait, WaitFor};
+use crate::DockerTestError;
+
+use bollard::container::InspectContainerOptions;
+use bollard::models::HealthcheckResult;
+use tokio::time::{interval, Duration};
+
+/// The HealthyWait `WaitFor` implementation for containers.
+/// This variant will wait until the docker daemon reports the container as healthy.
+///
+/// The container must have a healthcheck, either through the `HEALTHCHECK` of its image or
+/// one set on the container specification through `set_healthcheck`.
+/// Waiting fails as soon as the container is reported unhealthy, reporting the output of the
+/// most recent health probes.
+#[derive(Clone, Debug)]
+pub struct HealthyWait {
+    /// How many seconds shall there be between each check for the health status.
+    pub check_interval: u64,
+    /// The number of checks to perform before erroring out.
+    pub max_checks: u64,
+}
+
+#[async_trait]
+impl WaitFor for HealthyWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        let mut interval = interval(Duration::from_secs(self.check_interval));
+        let mut probes = Vec::new();
+
+        for _ in 0..self.max_checks {
+            interval.tick().await;
+
+            let details = container
+                .client
+                .inspect_container(&container.id, None::<InspectContainerOptions>)
+                .await
+                .map_err(|e| {
+                    DockerTestError::Daemon(format!("failed to inspect container: {}", e))
+                })?;
+            let state = details.state.unwrap_or_default();
+            let health = state.health.unwrap_or_default();
+            probes = health.log.unwrap_or_default();
+
+            match HealthStatus::from_inspect(health.status) {
+                Some(HealthStatus::Healthy) => return Ok(container.into()),
+                Some(HealthStatus::Unhealthy) => {
+                    return Err(DockerTestError::Startup(format!(
+                        "container is unhealthy{}",
+                        display_probes(&probes)
+                    )))
+                }
+                Some(HealthStatus::Starting) => (),
+                None => {
+                    return Err(DockerTestError::Startup(
+                        "container has no healthcheck".to_string(),
+                    ))
+                }
+            }
+
+            if state.running == Some(false) {
+                return Err(DockerTestError::Startup(format!(
+                    "container exited before becoming healthy{}",
+                    display_probes(&probes)
+                )));
+            }
+        }
+
+        Err(DockerTestError::Startup(format!(
+            "container did not become healthy within {} checks{}",
+            self.max_checks,
+            display_probes(&probes)
+        )))
+    }
+}
+
+/// Describe the exit code and output of each health probe, oldest first.
+fn display_probes(probes: &[HealthcheckResult]) -> String {
+    probes
+        .iter()
+        .map(|p| {
+            format!(
+                "; probe exited with {}: {}",
+                p.exit_code.unwrap_or_default(),
+                p.output.as_deref().unwrap_or_default().trim()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::waitfor::health::display_probes;
+
+    use bollard::models::HealthcheckResult;
+
+    #[test]
+    fn test_display_probes() {
+        let probe = |exit_code, output: &str| HealthcheckResult {
+            exit_code: Some(exit_code),
+            output: Some(output.to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(display_probes(&[]), "");
+        assert_eq!(
+            display_probes(&[probe(1, "connection refused\n"), probe(0, "")]),
+            "; probe exited with 1: connection refused; probe exited with 0: "
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! `WaitFor` implementation awaiting an HTTP endpoint of a container.
+
+use crate::container::{PendingContainer, RunningContainer};
+use crate::waitfor::{async_trait, tcp::port_addresses, WaitFor};
+use crate::DockerTestError;
+
+use regex::Regex;
+use tokio::time::{sleep, timeout, Duration, Instant};
+
+/// The HttpWait `WaitFor` implementation for containers.
+/// This variant will issue HTTP GET requests to a container port until the response has the
+/// expected status code, and optionally a body matching a substring or regular expression.
+///
+/// The port is contacted the same way as the [TcpPortWait], through its published host port if
+/// any, and otherwise through the ip of the container. Since the request is issued to an ip
+/// address, HTTPS endpoints commonly require [HttpWait::with_insecure] to skip certificate
+/// verification.
+///
+/// Requests are never sent through a proxy configured by the environment.
+///
+/// Requires the `http` feature.
+///
+/// [TcpPortWait]: crate::waitfor::TcpPortWait
+#[derive(Clone, Debug)]
+pub struct HttpWait {
+    port: u32,
+    path: String,
+    https: bool,
+    insecure: bool,
+    status: u16,
+    body: Option<BodyPattern>,
+    headers: Vec<(String, String)>,
+    timeout: Duration,
+    interval: Duration,
+}
+
+#[derive(Clone, Debug)]
+enum BodyPattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl HttpWait {
+    /// Creates an [HttpWait] requesting `/` on the given container port over HTTP, expecting
+    /// status code 200 within 30 seconds.
+    pub fn new(port: u32) -> HttpWait {
+        HttpWait {
+            port,
+            path: "/".to_string(),
+            https: false,
+            insecure: false,
+            status: 200,
+            body: None,
+            headers: Vec::new(),
+            timeout: Duration::from_secs(30),
+            interval: Duration::from_millis(250),
+        }
+    }
+
+    /// Sets the path to request, e.g., `/health`.
+    pub fn with_path<T: ToString>(self, path: T) -> HttpWait {
+        let path = path.to_string();
+        let path = if path.starts_with('/') {
+            path
+        } else {
+            format!("/{}", path)
+        };
+        HttpWait { path, ..self }
+    }
+
+    /// Sets the expected status code of the response, defaults to 200.
+    pub fn with_status(self, status: u16) -> HttpWait {
+        HttpWait { status, ..self }
+    }
+
+    /// Require the response body to contain the given substring.
+    pub fn with_body<T: Into<String>>(self, substring: T) -> HttpWait {
+        HttpWait {
+            body: Some(BodyPattern::Substring(substring.into())),
+            ..self
+        }
+    }
+
+    /// Require the response body to match the given regular expression.
+    ///
+    /// Fails if the regular expression is invalid.
+    pub fn with_body_regex(self, pattern: &str) -> Result<HttpWait, DockerTestError> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            DockerTestError::Processing(format!("invalid body regex `{}`: {}", pattern, e))
+        })?;
+
+        Ok(HttpWait {
+            body: Some(BodyPattern::Regex(regex)),
+            ..self
+        })
+    }
+
+    /// Add a header to each request.
+    pub fn with_header<T: ToString, S: ToString>(mut self, name: T, value: S) -> HttpWait {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Issue requests over HTTPS instead of HTTP.
+    pub fn with_https(self, https: bool) -> HttpWait {
+        HttpWait { https, ..self }
+    }
+
+    /// Skip verification of the certificate presented over HTTPS.
+    pub fn with_insecure(self, insecure: bool) -> HttpWait {
+        HttpWait { insecure, ..self }
+    }
+
+    /// Sets the duration to await the expected response before erroring out.
+    pub fn with_timeout(self, timeout: Duration) -> HttpWait {
+        HttpWait { timeout, ..self }
+    }
+
+    /// Sets the duration between each request.
+    pub fn with_interval(self, interval: Duration) -> HttpWait {
+        HttpWait { interval, ..self }
+    }
+
+    /// Returns whether the response satisfies the expected status code and body.
+    fn is_satisfied(&self, status: u16, body: &str) -> bool {
+        status == self.status
+            && match &self.body {
+                Some(BodyPattern::Substring(substring)) => body.contains(substring.as_str()),
+                Some(BodyPattern::Regex(regex)) => regex.is_match(body),
+                None => true,
+            }
+    }
+
+    /// Issue a single request, returning the status code and body of the response.
+    async fn request(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<(u16, String), reqwest::Error> {
+        let request = self
+            .headers
+            .iter()
+            .fold(client.get(url), |r, (name, value)| r.header(name, value));
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let body = response.text().await?;
+        Ok((status, body))
+    }
+}
+
+#[async_trait]
+impl WaitFor for HttpWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        // Proxies configured by the environment are commonly unable to reach the container.
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .danger_accept_invalid_certs(self.insecure)
+            .build()
+            .map_err(|e| {
+                DockerTestError::Processing(format!("failed to construct http client: {}", e))
+            })?;
+        let scheme = if self.https { "https" } else { "http" };
+
+        let deadline = Instant::now() + self.timeout;
+        let mut last = "no address to request".to_string();
+
+        loop {
+            // Addresses are resolved anew for each attempt, as the container may not have been
+            // assigned an ip yet.
+            for address in port_addresses(&container, self.port).await? {
+                let url = format!("{}://{}{}", scheme, address, self.path);
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                last = match timeout(remaining, self.request(&client, &url)).await {
+                    Ok(Ok((status, body))) if self.is_satisfied(status, &body) => {
+                        return Ok(container.into())
+                    }
+                    Ok(Ok((status, body))) => format!(
+                        "`{}` responded with status {}: {}",
+                        url,
+                        status,
+                        body.chars().take(256).collect::<String>()
+                    ),
+                    Ok(Err(e)) if e.is_builder() => {
+                        return Err(DockerTestError::Processing(format!(
+                            "invalid http request to `{}`: {}",
+                            url, e
+                        )))
+                    }
+                    Ok(Err(e)) => format!("request to `{}` failed: {}", url, e),
+                    Err(_) => format!("request to `{}` timed out", url),
+                };
+            }
+
+            if Instant::now() + self.interval > deadline {
+                return Err(DockerTestError::Startup(format!(
+                    "http endpoint on port {} not ready within {:?}, last attempt: {}",
+                    self.port, self.timeout, last
+                )));
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::waitfor::HttpWait;
+
+    #[test]
+    fn test_http_wait_is_satisfied() {
+        let wait = HttpWait::new(80);
+        assert!(wait.is_satisfied(200, ""));
+        assert!(!wait.is_satisfied(503, ""));
+
+        let wait = HttpWait::new(80).with_status(204).with_body("ready");
+        assert!(wait.is_satisfied(204, "service is ready"));
+        assert!(!wait.is_satisfied(204, "service is starting"));
+        assert!(!wait.is_satisfied(200, "service is ready"));
+
+        let wait = HttpWait::new(80)
+            .with_body_regex(r#""status":\s*"UP""#)
+            .unwrap();
+        assert!(wait.is_satisfied(200, r#"{"status": "UP"}"#));
+        assert!(!wait.is_satisfied(200, r#"{"status": "DOWN"}"#));
+
+        assert!(HttpWait::new(80).with_body_regex("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_http_wait_path() {
+        assert_eq!(HttpWait::new(80).with_path("health").path, "/health");
+        assert_eq!(HttpWait::new(80).with_path("/health").path, "/health");
+    }
+}
@@ -0,0 +1,123 @@
+//! Match log lines of a container against a pattern.
+
+use crate::DockerTestError;
+
+use regex::Regex;
+
+use std::fmt;
+
+/// Describes which log lines of a container satisfy a message condition.
+///
+/// Each line of log output is matched individually, without its trailing newline. The condition
+/// is satisfied once the configured number of lines have matched.
+#[derive(Clone, Debug)]
+pub struct LogMatcher {
+    pattern: Pattern,
+    occurrences: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Pattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl LogMatcher {
+    /// Match lines containing the given message.
+    pub fn substring<T: Into<String>>(message: T) -> LogMatcher {
+        LogMatcher {
+            pattern: Pattern::Substring(message.into()),
+            occurrences: 1,
+        }
+    }
+
+    /// Match lines matching the given regular expression.
+    ///
+    /// Fails if the regular expression is invalid.
+    pub fn regex(pattern: &str) -> Result<LogMatcher, DockerTestError> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            DockerTestError::Processing(format!("invalid log regex `{}`: {}", pattern, e))
+        })?;
+
+        Ok(LogMatcher {
+            pattern: Pattern::Regex(regex),
+            occurrences: 1,
+        })
+    }
+
+    /// Require the given number of matching lines, defaults to one.
+    pub fn occurrences(mut self, occurrences: usize) -> LogMatcher {
+        self.occurrences = occurrences.max(1);
+        self
+    }
+
+    /// Returns whether the given line matches the pattern.
+    pub fn is_match(&self, line: &str) -> bool {
+        match &self.pattern {
+            Pattern::Substring(message) => line.contains(message.as_str()),
+            Pattern::Regex(regex) => regex.is_match(line),
+        }
+    }
+
+    /// Returns the number of matching lines required to satisfy the condition.
+    pub(crate) fn required_occurrences(&self) -> usize {
+        self.occurrences
+    }
+}
+
+impl From<String> for LogMatcher {
+    fn from(message: String) -> LogMatcher {
+        LogMatcher::substring(message)
+    }
+}
+
+impl From<&str> for LogMatcher {
+    fn from(message: &str) -> LogMatcher {
+        LogMatcher::substring(message)
+    }
+}
+
+impl fmt::Display for LogMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.pattern {
+            Pattern::Substring(message) => write!(f, "{}", message)?,
+            Pattern::Regex(regex) => write!(f, "regex {}", regex)?,
+        }
+        if self.occurrences > 1 {
+            write!(f, " ({} occurrences)", self.occurrences)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::waitfor::LogMatcher;
+
+    #[test]
+    fn test_substring_matcher() {
+        let matcher = LogMatcher::substring("ready");
+        assert!(matcher.is_match("server is ready to accept connections"));
+        assert!(!matcher.is_match("server is starting"));
+        assert_eq!(matcher.to_string(), "ready");
+    }
+
+    #[test]
+    fn test_regex_matcher() {
+        let matcher = LogMatcher::regex(r"listening on port \d+")
+            .unwrap()
+            .occurrences(2);
+        assert!(matcher.is_match("listening on port 5432"));
+        assert!(!matcher.is_match("listening on port"));
+        assert_eq!(matcher.required_occurrences(), 2);
+        assert_eq!(
+            matcher.to_string(),
+            r"regex listening on port \d+ (2 occurrences)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_regex_matcher() {
+        assert!(LogMatcher::regex("(unclosed").is_err());
+    }
+}
@@ -1,20 +1,18 @@
-use crate::container::{PendingContainer, RunningContainer};
-use crate::waitfor::{async_trait, WaitFor};
-use crate::DockerTestError;
+use crate::container::{logs, PendingContainer, RunningContainer};
+use crate::waitfor::{async_trait, LogMatcher, WaitFor};
+use crate::{DockerTestError, LogSource, LogStreamOptions};
 
-use bollard::{
-    container::{LogOutput, LogsOptions},
-    Docker,
-};
+use bollard::Docker;
 use futures::stream::StreamExt;
-use serde::Serialize;
-use std::sync::atomic::{self, AtomicBool};
-use std::sync::Arc;
 use tokio::{time, time::Duration};
 use tracing::{event, Level};
 
+use std::time::SystemTime;
+
 /// The MessageWait `WaitFor` implementation for containers.
-/// This variant will wait until the message appears in the requested source.
+/// This variant will wait until a log line of the requested source contains the message.
+///
+/// Use [LogMatcherWait] to await regular expressions or multiple occurrences.
 #[derive(Clone, Debug)]
 pub struct MessageWait {
     /// The message to be contained in source.
@@ -44,7 +42,35 @@ impl WaitFor for MessageWait {
         pending_container_wait_for_message(
             container,
             self.source,
-            self.message.clone(),
+            LogMatcher::substring(self.message.clone()),
+            self.timeout,
+        )
+        .await
+    }
+}
+
+/// The LogMatcherWait `WaitFor` implementation for containers.
+/// This variant will wait until the source emits log lines satisfying the [LogMatcher].
+#[derive(Clone, Debug)]
+pub struct LogMatcherWait {
+    /// The condition the log lines of the source must satisfy.
+    pub matcher: LogMatcher,
+    /// The source to listen for matching log lines.
+    pub source: MessageSource,
+    /// Number of seconds to wait for the matching log lines. Times out with an error on expire.
+    pub timeout: u16,
+}
+
+#[async_trait]
+impl WaitFor for LogMatcherWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        pending_container_wait_for_message(
+            container,
+            self.source,
+            self.matcher.clone(),
             self.timeout,
         )
         .await
@@ -54,7 +80,7 @@ impl WaitFor for MessageWait {
 async fn pending_container_wait_for_message(
     container: PendingContainer,
     source: MessageSource,
-    msg: String,
+    matcher: LogMatcher,
     timeout: u16,
 ) -> Result<RunningContainer, DockerTestError> {
     // Must unfortunately clone the client, since the PendingContainer will be consusumed.
@@ -64,8 +90,9 @@ async fn pending_container_wait_for_message(
         &container.id,
         &container.handle,
         source,
-        msg,
+        &matcher,
         timeout,
+        container.logs_since,
     )
     .await
     {
@@ -74,79 +101,55 @@ async fn pending_container_wait_for_message(
     }
 }
 
-pub(crate) async fn wait_for_message<T>(
+/// Await the log lines of the source satisfying the matcher, returning the matching lines.
+///
+/// Only log lines emitted at or after `since` are considered, if given.
+pub(crate) async fn wait_for_message(
     client: &Docker,
     container_id: &str,
     handle: &str,
     source: MessageSource,
-    msg: T,
+    matcher: &LogMatcher,
     timeout: u16,
-) -> Result<(), DockerTestError>
-where
-    T: Into<String> + Serialize,
-{
-    // Construct LogOptions
-    let mut log_options = LogsOptions::<String> {
-        follow: true,
-        ..Default::default()
+    since: Option<SystemTime>,
+) -> Result<Vec<String>, DockerTestError> {
+    let source = match source {
+        MessageSource::Stdout => LogSource::StdOut,
+        MessageSource::Stderr => LogSource::StdErr,
     };
-    match source {
-        MessageSource::Stdout => log_options.stdout = true,
-        MessageSource::Stderr => log_options.stderr = true,
+    let options = LogStreamOptions::new()
+        .with_source(source)
+        .with_follow(true);
+    let options = match since {
+        Some(since) => options.with_precise_since(since),
+        None => options,
     };
-    let log_options = Some(log_options);
 
-    // Construct remaining variables
-    let s1 = Arc::new(AtomicBool::new(false));
-    let s2 = s1.clone();
-    let msg_clone1: String = msg.into();
-    let msg_clone2: String = msg_clone1.clone();
-
-    // Construct the stream
-    let stream = client.logs(container_id, log_options);
-
-    // Work configuration
+    let mut matched = Vec::new();
     let work_fut = async {
-        stream
-            .take_while(move |chunk| {
-                match chunk {
-                    Ok(chunk) => {
-                        // Extract the String from LogOutput variants
-                        let content = match chunk {
-                            LogOutput::StdErr { message } => Some(message),
-                            LogOutput::StdOut { message } => Some(message),
-                            LogOutput::StdIn { message: _ } => None,
-                            LogOutput::Console { message: _ } => None,
-                        };
-                        match content {
-                            Some(content)
-                                if String::from_utf8(content.to_vec())
-                                    .unwrap()
-                                    .contains(&msg_clone1) =>
-                            {
-                                s1.store(true, atomic::Ordering::SeqCst);
-                                futures::future::ready(false)
-                            }
-                            _ => futures::future::ready(true),
-                        }
+        let mut stream = logs::logs(client, container_id, &options);
+        while let Some(line) = stream.next().await {
+            match line {
+                Ok(line) if matcher.is_match(line.message()) => {
+                    matched.push(line.message().to_string());
+                    if matched.len() >= matcher.required_occurrences() {
+                        return Ok(true);
                     }
-                    Err(_) => futures::future::ready(false),
                 }
-            })
-            .collect::<Vec<_>>()
-            .await
+                Ok(_) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(false)
     };
 
     match time::timeout(Duration::from_secs(timeout.into()), work_fut).await {
-        Ok(_) => {
-            if s2.load(atomic::Ordering::SeqCst) {
-                Ok(())
-            } else {
-                Err(DockerTestError::Startup(
-                   format!("container `{}` ended log stream (terminated) before waitfor message triggered: `{}`", handle, msg_clone2),
-                ))
-            }
-        }
+        Ok(Ok(true)) => Ok(matched),
+        Ok(Err(e)) => Err(e),
+        Ok(Ok(false)) => Err(DockerTestError::Startup(format!(
+            "container `{}` ended log stream (terminated) before waitfor message triggered: `{}`",
+            handle, matcher
+        ))),
         Err(_) => {
             event!(Level::WARN, "awaiting container message timed out");
             Err(DockerTestError::Startup(
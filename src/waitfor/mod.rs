@@ -7,14 +7,28 @@ use crate::DockerTestError;
 pub use async_trait::async_trait;
 use dyn_clone::DynClone;
 
+mod combinator;
+mod exec;
+mod health;
+#[cfg(feature = "http")]
+mod http;
+mod matcher;
 mod message;
 mod nowait;
 mod status;
+mod tcp;
 
+pub use combinator::{AllOf, AnyOf, Sequence};
+pub use exec::ExecWait;
+pub use health::HealthyWait;
+#[cfg(feature = "http")]
+pub use http::HttpWait;
+pub use matcher::LogMatcher;
 pub(crate) use message::wait_for_message;
-pub use message::{MessageSource, MessageWait};
+pub use message::{LogMatcherWait, MessageSource, MessageWait};
 pub use nowait::NoWait;
 pub use status::{ExitedWait, RunningWait};
+pub use tcp::TcpPortWait;
 
 /// Trait to wait for a container to be ready for service.
 #[async_trait]
@@ -28,6 +42,16 @@ pub trait WaitFor: Send + Sync + DynClone + std::fmt::Debug {
         &self,
         container: PendingContainer,
     ) -> Result<RunningContainer, DockerTestError>;
+
+    /// Method implementation should return a future that resolves once the condition is
+    /// fulfilled, without consuming the container.
+    ///
+    /// This allows combinators such as [AllOf] to check the condition concurrently with, or
+    /// after, other conditions of the same container. The default implementation invokes
+    /// [WaitFor::wait_for_ready] with a copy of the container.
+    async fn check_ready(&self, container: &PendingContainer) -> Result<(), DockerTestError> {
+        self.wait_for_ready(container.clone()).await.map(|_| ())
+    }
 }
 
 dyn_clone::clone_trait_object!(WaitFor);
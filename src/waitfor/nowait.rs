@@ -42,6 +42,8 @@ mod tests {
             handle_key,
             StartPolicy::Relaxed,
             wait.clone(),
+            None,
+            None,
             client,
             None,
             None,
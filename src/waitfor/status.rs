@@ -20,6 +20,9 @@ pub struct RunningWait {
 
 /// The ExitedWait `WaitFor` implementation for containers.
 /// This variant will wait until the docker daemon reports that the container has exited.
+///
+/// The container is considered ready regardless of its exit code, use
+/// [RunningContainer::wait_exit] within the test body to retrieve it.
 #[derive(Clone, Debug)]
 pub struct ExitedWait {
     /// How many seconds shall there be between each check for running state.
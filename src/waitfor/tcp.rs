@@ -0,0 +1,182 @@
+//! `WaitFor` implementation awaiting a container port accepting connections.
+
+use crate::container::{HostPortMappings, PendingContainer, RunningContainer};
+use crate::runner::own_container_id;
+use crate::waitfor::{async_trait, WaitFor};
+use crate::DockerTestError;
+
+use bollard::container::InspectContainerOptions;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+use tokio::time::{sleep, timeout, Duration};
+
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+/// The TcpPortWait `WaitFor` implementation for containers.
+/// This variant will wait until the given container port accepts TCP connections.
+///
+/// The port is contacted through its published host port if any, and otherwise through the
+/// ip of the container on the test network. When dockertest itself runs within a container,
+/// the ip of the container is preferred, as published host ports are commonly unreachable.
+#[derive(Clone, Debug)]
+pub struct TcpPortWait {
+    /// The exposed container port to connect to.
+    pub port: u32,
+    /// The duration to await the port accepting connections before erroring out.
+    pub timeout: Duration,
+    /// The duration between each connection attempt.
+    pub interval: Duration,
+}
+
+#[async_trait]
+impl WaitFor for TcpPortWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        let mut tried = Vec::new();
+        match timeout(self.timeout, self.await_port(&container, &mut tried)).await {
+            Ok(Ok(())) => Ok(container.into()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => {
+                let tried: Vec<String> = tried.iter().map(|a| a.to_string()).collect();
+                Err(DockerTestError::Startup(format!(
+                    "port {} did not accept connections within {:?}, tried `{}`",
+                    self.port,
+                    self.timeout,
+                    tried.join("`, `")
+                )))
+            }
+        }
+    }
+}
+
+impl TcpPortWait {
+    /// Attempt to connect to the port until it accepts connections, recording the addresses
+    /// of the latest attempt.
+    async fn await_port(
+        &self,
+        container: &PendingContainer,
+        tried: &mut Vec<SocketAddr>,
+    ) -> Result<(), DockerTestError> {
+        loop {
+            // Addresses are resolved anew for each attempt, as the container may not have been
+            // assigned an ip yet.
+            *tried = port_addresses(container, self.port).await?;
+            for address in tried.iter() {
+                if accepts_connection(*address, self.interval).await {
+                    return Ok(());
+                }
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
+
+/// Resolve the addresses the given container port is reachable at from dockertest, in order
+/// of preference, the same way the test body would through [RunningContainer::host_port] and
+/// [RunningContainer::ip].
+///
+/// Fails if the container is no longer running.
+pub(crate) async fn port_addresses(
+    container: &PendingContainer,
+    port: u32,
+) -> Result<Vec<SocketAddr>, DockerTestError> {
+    let details = container
+        .client
+        .inspect_container(&container.id, None::<InspectContainerOptions>)
+        .await
+        .map_err(|e| DockerTestError::Daemon(format!("failed to inspect container: {}", e)))?;
+
+    if details.state.and_then(|s| s.running) == Some(false) {
+        return Err(DockerTestError::Startup(format!(
+            "container exited before port {} became reachable",
+            port
+        )));
+    }
+
+    let network_settings = details.network_settings.unwrap_or_default();
+    let published = match network_settings.ports {
+        Some(ports) => HostPortMappings::try_from(ports)
+            .map_err(|e| DockerTestError::HostPort(e.to_string()))?
+            .get(port)
+            .map(|(ip, host_port)| {
+                // Ports published on all interfaces are reachable through localhost.
+                let ip = if ip.is_unspecified() {
+                    Ipv4Addr::LOCALHOST
+                } else {
+                    ip
+                };
+                SocketAddr::V4(SocketAddrV4::new(ip, host_port as u16))
+            }),
+        None => None,
+    };
+    // Only the test network is reachable, as the container may be attached to other networks.
+    let networks = network_settings
+        .networks
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(name, n)| match &container.network {
+            Some(network) => name == network || n.network_id.as_ref() == Some(network),
+            None => true,
+        })
+        .filter_map(|(_, n)| n.ip_address?.parse::<Ipv4Addr>().ok())
+        .map(|ip| SocketAddr::V4(SocketAddrV4::new(ip, port as u16)));
+
+    let addresses = if own_container_id().is_some() {
+        networks.chain(published).collect()
+    } else {
+        published.into_iter().chain(networks).collect()
+    };
+
+    Ok(addresses)
+}
+
+/// Returns whether a service accepts connections at the given address.
+async fn accepts_connection(address: SocketAddr, connect_timeout: Duration) -> bool {
+    let mut stream = match timeout(connect_timeout, TcpStream::connect(address)).await {
+        Ok(Ok(stream)) => stream,
+        _ => return false,
+    };
+
+    // The docker userland proxy accepts connections on published ports before the service
+    // within the container is listening, immediately closing them instead. We therefore
+    // consider a connection closed without any data as refused.
+    let mut buf = [0u8; 1];
+    !matches!(
+        timeout(Duration::from_millis(100), stream.read(&mut buf)).await,
+        Ok(Ok(0))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::waitfor::tcp::accepts_connection;
+
+    use tokio::net::TcpListener;
+    use tokio::time::Duration;
+
+    #[tokio::test]
+    async fn test_accepts_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let timeout = Duration::from_secs(1);
+
+        // Keep the accepted connection open, as a listening service would.
+        let accept = tokio::spawn(async move { listener.accept().await.unwrap() });
+        assert!(accepts_connection(address, timeout).await);
+
+        // Connections closed without any data are considered refused.
+        let closing = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closing_address = closing.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (stream, _) = closing.accept().await.unwrap();
+            drop(stream);
+        });
+        assert!(!accepts_connection(closing_address, timeout).await);
+
+        drop(accept.await.unwrap());
+        assert!(!accepts_connection(address, timeout).await);
+    }
+}
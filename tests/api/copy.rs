@@ -0,0 +1,72 @@
+use crate::helper::sleep_container;
+use dockertest::{DockerTest, Source};
+use test_log::test;
+
+// Tests that a file written into the container can be read back.
+#[test]
+fn test_write_and_read_file() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        container
+            .write_file("/tmp/fixture.conf", "key = value")
+            .await
+            .expect("failed to write file");
+
+        let output = container
+            .exec(vec!["cat", "/tmp/fixture.conf"])
+            .await
+            .expect("failed to exec command");
+        assert_eq!(output.stdout_lossy(), "key = value");
+
+        let contents = container
+            .read_file_to_string("/tmp/fixture.conf")
+            .await
+            .expect("failed to read file");
+        assert_eq!(contents, "key = value");
+    });
+}
+
+// Tests that a host directory is copied recursively into the container.
+#[test]
+fn test_copy_directory_to_container() {
+    let dir = std::env::temp_dir().join("dockertest-rs-copy-to");
+    std::fs::create_dir_all(dir.join("nested")).expect("failed to create directory");
+    std::fs::write(dir.join("nested/file.txt"), "nested").expect("failed to write file");
+
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        container
+            .copy_to(&dir, "/tmp/fixtures")
+            .await
+            .expect("failed to copy directory");
+
+        let contents = container
+            .read_file_to_string("/tmp/fixtures/nested/file.txt")
+            .await
+            .expect("failed to read file");
+        assert_eq!(contents, "nested");
+    });
+}
+
+// Tests that reading a directory as a file fails.
+#[test]
+fn test_read_file_rejects_directory() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        assert!(container.read_file("/tmp").await.is_err());
+        assert!(!container
+            .copy_from("/tmp")
+            .await
+            .expect("failed to copy directory")
+            .is_empty());
+    });
+}
@@ -0,0 +1,62 @@
+use crate::helper::sleep_container;
+use dockertest::{DockerTest, ExecOptions, Source};
+use std::time::Duration;
+use test_log::test;
+
+// Tests that exec captures stdout, stderr and the exit code of the command.
+#[test]
+fn test_exec_captures_output_and_exit_code() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        let output = container
+            .exec(vec!["sh", "-c", "echo out; echo err >&2; exit 3"])
+            .await
+            .expect("failed to exec command");
+
+        assert_eq!(output.stdout_lossy(), "out\n");
+        assert_eq!(output.stderr_lossy(), "err\n");
+        assert_eq!(output.exit_code, 3);
+        assert!(!output.success());
+    });
+}
+
+// Tests that the exec options are applied to the executed command.
+#[test]
+fn test_exec_with_options() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        let options = ExecOptions::new(vec!["sh", "-c", "echo $GREETING $(pwd); cat"])
+            .with_env("GREETING", "hello")
+            .with_workdir("/tmp")
+            .with_stdin("from stdin");
+        let output = container
+            .exec_with(options)
+            .await
+            .expect("failed to exec command");
+
+        assert!(output.success());
+        assert_eq!(output.stdout_lossy(), "hello /tmp\nfrom stdin");
+    });
+}
+
+// Tests that a command exceeding its timeout fails.
+#[test]
+fn test_exec_timeout() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        let options =
+            ExecOptions::new(vec!["sleep", "10"]).with_timeout(Duration::from_millis(500));
+        let result = container.exec_with(options).await;
+
+        assert!(result.is_err(), "exec did not time out");
+    });
+}
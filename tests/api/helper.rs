@@ -1,5 +1,8 @@
 use bollard::Docker;
-use dockertest::{utils::connect_with_local_or_tls_defaults, RunningContainer};
+use dockertest::waitfor::RunningWait;
+use dockertest::{
+    utils::connect_with_local_or_tls_defaults, RunningContainer, TestBodySpecification,
+};
 
 pub struct TestHelper {
     client: Docker,
@@ -34,4 +37,25 @@ impl TestHelper {
             .unwrap()
             .cmd
     }
+
+    /// Returns whether the container with the given id exists on the docker daemon.
+    pub async fn container_exists(&self, id: &str) -> bool {
+        match self.client.inspect_container(id, None).await {
+            Ok(_) => true,
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 404, ..
+            }) => false,
+            Err(e) => panic!("failed to inspect container: {}", e),
+        }
+    }
+}
+
+/// A long-running container, ready once it is running.
+pub fn sleep_container(handle: &str) -> TestBodySpecification {
+    TestBodySpecification::with_repository("luca3m/sleep")
+        .set_handle(handle)
+        .set_wait_for(Box::new(RunningWait {
+            max_checks: 10,
+            check_interval: 1,
+        }))
 }
@@ -0,0 +1,113 @@
+use dockertest::waitfor::RunningWait;
+use dockertest::{DockerTest, Source, TestBodySpecification, TestSuiteSpecification};
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_log::test;
+use tokio::sync::Barrier;
+use tokio::time::timeout;
+
+// Tests that the on_ready hook observes the inspected container before the test body runs.
+#[test]
+fn test_on_ready_invoked_before_test_body() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let invocations = Arc::new(AtomicU32::new(0));
+    let counter = invocations.clone();
+
+    let repo = "luca3m/sleep";
+    let sleep_container = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(RunningWait {
+            max_checks: 10,
+            check_interval: 1,
+        }))
+        .set_on_ready(move |container| {
+            let counter = counter.clone();
+            async move {
+                if container.ip() == Ipv4Addr::UNSPECIFIED {
+                    return Err("container was not inspected");
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+    test.provide_container(sleep_container);
+
+    let observed = invocations.clone();
+    test.run(|_ops| async move {
+        assert_eq!(
+            observed.load(Ordering::SeqCst),
+            1,
+            "on_ready hook was not invoked prior to the test body"
+        );
+    });
+}
+
+// Tests that a failing on_ready hook is reported as a startup failure.
+#[test]
+#[should_panic(expected = "container `hello-world` failed during on_ready hook")]
+fn test_on_ready_failure_fails_startup() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let hello_world = TestBodySpecification::with_repository(repo)
+        .set_on_ready(|_container| async move { Err("seeding failed") });
+    test.provide_container(hello_world);
+
+    test.run(|_ops| async move {
+        panic!("test body shall not be invoked");
+    });
+}
+
+// Tests that the on_ready hook of a static container is only invoked once, when concurrent
+// tests share the container.
+#[tokio::test]
+async fn test_on_ready_invoked_once_for_static_container() {
+    let invocations = Arc::new(AtomicU32::new(0));
+    let ids = Arc::new(Mutex::new(Vec::new()));
+    // Both test bodies await each other, such that neither test removes the container
+    // before the other has started using it.
+    let barrier = Arc::new(Barrier::new(2));
+
+    let run = || {
+        let source = Source::DockerHub;
+        let mut test = DockerTest::new().with_default_source(source);
+
+        let counter = invocations.clone();
+        let repo = "luca3m/sleep";
+        let sleep_container = TestSuiteSpecification::with_repository(repo)
+            .set_handle("on_ready_static")
+            .set_on_ready(move |_container| {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok::<(), String>(())
+                }
+            });
+        test.provide_container(sleep_container);
+
+        let ids = ids.clone();
+        let barrier = barrier.clone();
+        test.run_async(|ops| async move {
+            let id = ops.handle("on_ready_static").id().to_string();
+            ids.lock().unwrap().push(id);
+            timeout(Duration::from_secs(60), barrier.wait())
+                .await
+                .expect("concurrent test did not start");
+        })
+    };
+
+    tokio::join!(run(), run());
+
+    let ids = ids.lock().unwrap();
+    assert_eq!(ids.len(), 2);
+    assert_eq!(ids[0], ids[1], "tests did not share the static container");
+    assert_eq!(
+        invocations.load(Ordering::SeqCst),
+        1,
+        "on_ready hook of static container was invoked more than once"
+    );
+}
@@ -2,6 +2,7 @@ use std::net::Ipv4Addr;
 
 use dockertest::waitfor::RunningWait;
 use dockertest::{DockerTest, Source, TestBodySpecification};
+use std::time::Duration;
 use test_log::test;
 
 use crate::helper::TestHelper;
@@ -35,6 +36,23 @@ fn test_run_with_failure() {
     });
 }
 
+#[test]
+#[should_panic(expected = "test body timed out after 2s, running containers: `hello-world`")]
+fn test_run_with_test_timeout() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new()
+        .with_default_source(source)
+        .with_test_timeout(Duration::from_secs(2));
+
+    let repo = "hello-world".to_string();
+    let hello_world = TestBodySpecification::with_repository(repo);
+    test.provide_container(hello_world);
+
+    test.run(|_ops| async move {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+    });
+}
+
 // Tests that we can retrieve the handle of a container by providing the repository as the key
 #[test]
 fn test_resolve_handle_with_repository_as_key() {
@@ -50,6 +68,48 @@ fn test_resolve_handle_with_repository_as_key() {
     });
 }
 
+// Tests that a report of the run is written to the configured path.
+#[test]
+fn test_run_writes_report() {
+    let path = std::env::temp_dir().join(format!(
+        "dockertest-rs-run-report-{}.json",
+        std::process::id()
+    ));
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new()
+        .with_default_source(source)
+        .with_report_path(&path);
+
+    let repo = "hello-world".to_string();
+    let hello_world = TestBodySpecification::with_repository(repo);
+    test.provide_container(hello_world);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+
+    let report = std::fs::read(&path).expect("report was not written");
+    std::fs::remove_file(&path).expect("failed to remove report");
+    let report: serde_json::Value =
+        serde_json::from_slice(&report).expect("report is not valid json");
+    assert_eq!(report["outcome"], "passed");
+
+    let container = &report["containers"][0];
+    assert_eq!(container["handle"], "hello-world");
+    assert_eq!(container["start_policy"], "relaxed");
+    assert!(container["id"].is_string(), "container id was not reported");
+    let phases: Vec<&str> = container["transitions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["phase"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        phases,
+        vec!["pulled", "created", "starting", "started", "inspected"]
+    );
+}
+
 // Tests that we fail to retrieve a handle for a container with an invalid key
 #[test]
 #[should_panic(
@@ -85,12 +145,10 @@ fn test_resolve_handle_with_user_provided_container_name_as_key() {
     });
 }
 
-// Tests that we fail to retrieve a handle for a container when multiple containers have the same
-// user provided container name
+// Tests that the test is rejected when multiple containers have the same user provided
+// container name
 #[test]
-#[should_panic(
-    expected = "test body failure `handle 'this_is_a_container_name' defined multiple times`"
-)]
+#[should_panic(expected = "handle `this_is_a_container_name` is used by multiple containers")]
 fn test_resolve_handle_with_identical_user_provided_container_name() {
     let source = Source::DockerHub;
     let mut test = DockerTest::new().with_default_source(source);
@@ -107,10 +165,9 @@ fn test_resolve_handle_with_identical_user_provided_container_name() {
     });
 }
 
-// Tests that we fail to retrieve a handle for a container when multiple containers have the same
-// repository name
+// Tests that the test is rejected when multiple containers have the same repository name
 #[test]
-#[should_panic(expected = "test body failure `handle 'hello-world' defined multiple times`")]
+#[should_panic(expected = "handle `hello-world` is used by multiple containers")]
 fn test_resolve_handle_with_identical_repository() {
     let source = Source::DockerHub;
     let mut test = DockerTest::new().with_default_source(source);
@@ -144,7 +201,7 @@ fn test_ip_on_running_container() {
         let handle = ops.handle(repo);
         // UNSPECIFIED is the default ip-addr.
         // - we simply check that we have populated with something else.
-        assert_ne!(handle.ip(), &std::net::Ipv4Addr::UNSPECIFIED);
+        assert_ne!(handle.ip(), std::net::Ipv4Addr::UNSPECIFIED);
     });
 }
 
@@ -254,7 +311,7 @@ fn test_ip_on_running_container_with_namespaced_instance() {
         let handle = ops.handle(repo);
         // UNSPECIFIED is the default ip-addr.
         // - we simply check that we have populated with something else.
-        assert_ne!(handle.ip(), &std::net::Ipv4Addr::UNSPECIFIED);
+        assert_ne!(handle.ip(), std::net::Ipv4Addr::UNSPECIFIED);
     });
 }
 
@@ -0,0 +1,248 @@
+use crate::helper::{sleep_container, TestHelper};
+use dockertest::waitfor::{ExitedWait, MessageSource, MessageWait, RunningWait};
+use dockertest::{
+    ContainerStatus, DockerTest, DockerTestError, LogLine, Source, TestBodySpecification,
+    TestSuiteSpecification,
+};
+use std::net::Ipv4Addr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_log::test;
+
+// Tests that a restarted container is running and re-inspected.
+#[test]
+fn test_restart_reinspects_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let mut container = sleep_container("sleep");
+    container.modify_port_map(7900, 8502);
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        container.restart().await.expect("failed to restart");
+
+        assert_ne!(container.ip(), Ipv4Addr::UNSPECIFIED);
+        assert_eq!(container.host_port(7900).map(|p| p.1), Some(8502));
+    });
+}
+
+// Tests that a restarted container awaits its log based WaitFor condition anew, rather than
+// observing the output of its previous run.
+#[test]
+fn test_restart_awaits_message_of_new_run() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let container = TestBodySpecification::with_repository("luca3m/sleep")
+        .set_handle("restart_message")
+        .replace_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "sleep 2; echo ready; sleep 60".to_string(),
+        ])
+        .set_wait_for(Box::new(MessageWait {
+            message: "ready".to_string(),
+            source: MessageSource::Stdout,
+            timeout: 30,
+        }));
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        let container = ops.handle("restart_message");
+        container.restart().await.expect("failed to restart");
+
+        let logs = container
+            .logs_snapshot()
+            .await
+            .expect("failed to retrieve logs");
+        let ready = logs.iter().filter(|l| l.message() == "ready").count();
+        assert_eq!(ready, 2, "restart completed before the new run was ready");
+    });
+}
+
+// Tests that a stopped container has no ip until it is started again.
+#[test]
+fn test_stop_and_start() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        container.stop().await.expect("failed to stop");
+        assert_eq!(container.ip(), Ipv4Addr::UNSPECIFIED);
+
+        container.start().await.expect("failed to start");
+        assert_ne!(container.ip(), Ipv4Addr::UNSPECIFIED);
+    });
+}
+
+// Tests that a killed container is no longer running.
+#[test]
+fn test_kill_with_signal() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        container.kill("SIGKILL").await.expect("failed to kill");
+        assert_eq!(container.ip(), Ipv4Addr::UNSPECIFIED);
+
+        let state = container.state().await.expect("failed to inspect state");
+        assert_eq!(state.status, ContainerStatus::Exited);
+        assert_eq!(state.exit_code, Some(137));
+        assert!(state.finished_at.is_some());
+    });
+}
+
+// Tests the state of a running container.
+#[test]
+fn test_state_of_running_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+        let state = container.state().await.expect("failed to inspect state");
+
+        assert_eq!(state.status, ContainerStatus::Running);
+        assert_eq!(state.exit_code, None);
+        assert!(!state.oom_killed);
+        assert_eq!(state.restart_count, 0);
+        assert_eq!(state.health, None);
+        assert!(state.started_at.is_some());
+    });
+}
+
+// Tests that an exited container reports its exit code, and refreshes to an unspecified ip.
+#[test]
+fn test_state_of_exited_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let hello =
+        TestBodySpecification::with_repository("hello-world").set_wait_for(Box::new(ExitedWait {
+            check_interval: 1,
+            max_checks: 10,
+        }));
+    test.provide_container(hello);
+
+    test.run(|ops| async move {
+        let container = ops.handle("hello-world");
+        let state = container.state().await.expect("failed to inspect state");
+        assert_eq!(state.status, ContainerStatus::Exited);
+        assert_eq!(state.exit_code, Some(0));
+
+        container.refresh().await.expect("failed to refresh");
+        assert_eq!(container.ip(), Ipv4Addr::UNSPECIFIED);
+    });
+}
+
+// Tests that the exit code and log output of a job container is retrieved.
+#[test]
+fn test_wait_exit_of_job_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let job = TestBodySpecification::with_repository("luca3m/sleep")
+        .set_handle("job")
+        .replace_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "sleep 1; echo migrated; echo failed >&2; exit 3".to_string(),
+        ])
+        .set_wait_for(Box::new(RunningWait {
+            max_checks: 10,
+            check_interval: 1,
+        }));
+    test.provide_container(job);
+
+    test.run(|ops| async move {
+        let status = ops
+            .handle("job")
+            .wait_exit(Duration::from_secs(30))
+            .await
+            .expect("failed to await exit");
+
+        assert!(!status.success());
+        assert_eq!(status.code, 3);
+        assert_eq!(
+            status.logs,
+            vec![
+                LogLine::StdOut("migrated".to_string()),
+                LogLine::StdErr("failed".to_string()),
+            ]
+        );
+    });
+}
+
+// Tests that awaiting the exit of a long running container times out.
+#[test]
+fn test_wait_exit_times_out() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let result = ops.handle("sleep").wait_exit(Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(DockerTestError::ExitTimeout { .. })));
+    });
+}
+
+// Tests that a container is frozen for the duration of the pause.
+#[test]
+fn test_pause_for_freezes_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let container = ops.handle("sleep");
+
+        let (paused, frozen) = tokio::join!(container.pause_for(Duration::from_secs(2)), async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            container.exec(vec!["true"]).await
+        });
+
+        paused.expect("failed to pause container");
+        assert!(frozen.is_err(), "exec succeeded within a paused container");
+        container
+            .exec(vec!["true"])
+            .await
+            .expect("container was not unpaused");
+    });
+}
+
+// Tests that a container left paused by the test body is torn down.
+#[tokio::test]
+async fn test_paused_container_is_torn_down() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    let id = Arc::new(Mutex::new(String::new()));
+    let paused = id.clone();
+    test.run_async(|ops| async move {
+        let container = ops.handle("sleep");
+        container.pause().await.expect("failed to pause container");
+        *paused.lock().unwrap() = container.id().to_string();
+    })
+    .await;
+
+    let id = id.lock().unwrap().clone();
+    assert!(
+        !TestHelper::new().container_exists(&id).await,
+        "paused container was not removed"
+    );
+}
+
+// Tests that static containers refuse to be restarted.
+#[test]
+fn test_restart_refused_for_static_container() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let container = TestSuiteSpecification::with_repository("luca3m/sleep")
+        .set_handle("lifecycle_static")
+        .set_wait_for(Box::new(RunningWait {
+            max_checks: 10,
+            check_interval: 1,
+        }));
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        let container = ops.handle("lifecycle_static");
+        assert!(matches!(
+            container.restart().await,
+            Err(DockerTestError::StaticContainer { .. })
+        ));
+    });
+}
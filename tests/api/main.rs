@@ -2,11 +2,17 @@
 #![deny(rust_2018_idioms)]
 
 mod annotation_test_runtime;
+mod copy;
+mod exec;
 mod helper;
+mod hooks;
 mod integration_test;
+mod lifecycle;
 mod message;
 mod multiple_containers;
+mod partition;
 mod privileged_container;
 mod readme;
 mod static_containers;
+mod stats;
 mod waitfor;
@@ -1,5 +1,6 @@
-use dockertest::waitfor::MessageSource;
-use dockertest::{DockerTest, TestBodySpecification};
+use dockertest::waitfor::{LogMatcher, MessageSource};
+use dockertest::{DockerTest, LogLine, LogSource, LogStreamOptions, TestBodySpecification};
+use futures::TryStreamExt;
 use test_log::test;
 
 #[test]
@@ -30,3 +31,71 @@ fn test_assert_message_in_test_body_panics_not_present() {
             .await;
     });
 }
+
+#[test]
+fn test_wait_for_message_returns_matching_lines() {
+    let mut test = DockerTest::new();
+    let composition = TestBodySpecification::with_repository("dockertest-rs/hello");
+    test.provide_container(composition);
+
+    test.run(|ops| async move {
+        let hello = ops.handle("dockertest-rs/hello");
+        let matcher = LogMatcher::regex("^hello .+$").expect("invalid regex");
+        let lines = hello
+            .wait_for_message(matcher, MessageSource::Stdout, 5)
+            .await
+            .expect("message was not present");
+        assert_eq!(lines, vec!["hello dockertest-rs".to_string()]);
+    });
+}
+
+#[test]
+fn test_wait_for_message_fails_not_present() {
+    let mut test = DockerTest::new();
+    let composition = TestBodySpecification::with_repository("dockertest-rs/hello");
+    test.provide_container(composition);
+
+    test.run(|ops| async move {
+        let hello = ops.handle("dockertest-rs/hello");
+        let result = hello
+            .wait_for_message("not present log message", MessageSource::Stdout, 1)
+            .await;
+        assert!(result.is_err());
+    });
+}
+
+#[test]
+fn test_logs_snapshot_contains_output() {
+    let mut test = DockerTest::new();
+    let composition = TestBodySpecification::with_repository("dockertest-rs/hello");
+    test.provide_container(composition);
+
+    test.run(|ops| async move {
+        let hello = ops.handle("dockertest-rs/hello");
+        let lines = hello.logs_snapshot().await.expect("failed to read logs");
+        assert!(lines
+            .iter()
+            .any(|l| matches!(l, LogLine::StdOut(m) if m.contains("hello dockertest-rs"))));
+    });
+}
+
+#[test]
+fn test_logs_stream_follows_output() {
+    let mut test = DockerTest::new();
+    let composition = TestBodySpecification::with_repository("dockertest-rs/hello");
+    test.provide_container(composition);
+
+    test.run(|ops| async move {
+        let hello = ops.handle("dockertest-rs/hello");
+        let options = LogStreamOptions::new()
+            .with_source(LogSource::StdOut)
+            .with_follow(true)
+            .with_tail(1);
+        let lines: Vec<LogLine> = hello
+            .logs(options)
+            .try_collect()
+            .await
+            .expect("failed to read logs");
+        assert_eq!(lines.len(), 1, "tail was not applied");
+    });
+}
@@ -32,3 +32,50 @@ fn test_inject_container_name_ip_through_env_communication() {
             .await;
     });
 }
+
+#[test]
+fn test_depends_on_container_name_communication() {
+    let mut test = DockerTest::new();
+
+    let recv = TestBodySpecification::with_repository("dockertest-rs/coop_recv")
+        .set_wait_for(Box::new(MessageWait {
+            message: "recv started".to_string(),
+            source: MessageSource::Stdout,
+            timeout: 10,
+        }))
+        .set_handle("recv");
+
+    let mut send = TestBodySpecification::with_repository("dockertest-rs/coop_send").set_wait_for(
+        Box::new(MessageWait {
+            message: "send success".to_string(),
+            source: MessageSource::Stdout,
+            timeout: 60,
+        }),
+    );
+    send.inject_container_name("recv", "SEND_TO_IP")
+        .depends_on("recv");
+
+    // Provide the dependant container first, the dependency graph dictates the start order.
+    test.provide_container(send).provide_container(recv);
+
+    test.run(|ops| async move {
+        let recv = ops.handle("recv");
+        recv.assert_message("coop send message to container", MessageSource::Stdout, 5)
+            .await;
+    });
+}
+
+#[test]
+#[should_panic(expected = "container dependency cycle detected `a -> b -> a`")]
+fn test_depends_on_cycle_fails() {
+    let mut test = DockerTest::new();
+
+    let mut a = TestBodySpecification::with_repository("dockertest-rs/hello").set_handle("a");
+    a.depends_on("b");
+    let mut b = TestBodySpecification::with_repository("dockertest-rs/hello").set_handle("b");
+    b.depends_on("a");
+
+    test.provide_container(a).provide_container(b);
+
+    test.run(|_ops| async move {});
+}
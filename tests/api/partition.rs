@@ -0,0 +1,66 @@
+use crate::helper::sleep_container;
+use dockertest::{DockerTest, DockerTestError, Source};
+use std::net::Ipv4Addr;
+use test_log::test;
+
+// Tests that a disconnected container loses its ip on the test network until reconnected.
+#[test]
+fn test_disconnect_and_reconnect() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("node"));
+
+    test.run(|ops| async move {
+        ops.disconnect("node").await.expect("failed to disconnect");
+        assert_eq!(ops.handle("node").ip(), Ipv4Addr::UNSPECIFIED);
+
+        ops.reconnect("node").await.expect("failed to reconnect");
+        assert_ne!(ops.handle("node").ip(), Ipv4Addr::UNSPECIFIED);
+    });
+}
+
+// Tests that partitioned containers can only reach containers within their own partition.
+#[test]
+fn test_partition_separates_groups() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("a"));
+    test.provide_container(sleep_container("b"));
+    test.provide_container(sleep_container("c"));
+
+    test.run(|ops| async move {
+        ops.partition(&["a", "b"], &["c"])
+            .await
+            .expect("failed to partition");
+        assert_ne!(ops.handle("a").ip(), Ipv4Addr::UNSPECIFIED);
+
+        let ping = |from: &'static str, to: &'static str| {
+            let ops = ops.clone();
+            async move {
+                let name = ops.handle(to).name().to_string();
+                ops.handle(from)
+                    .exec(vec!["ping", "-c", "1", "-W", "1", name.as_str()])
+                    .await
+                    .expect("failed to exec ping")
+                    .success()
+            }
+        };
+        assert!(ping("a", "b").await);
+        assert!(!ping("a", "c").await);
+
+        // The remaining containers are restored at teardown.
+        ops.reconnect("c").await.expect("failed to reconnect");
+        assert_ne!(ops.handle("c").ip(), Ipv4Addr::UNSPECIFIED);
+    });
+}
+
+// Tests that a container cannot be present in both partitions.
+#[test]
+fn test_partition_rejects_overlapping_groups() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("a"));
+    test.provide_container(sleep_container("b"));
+
+    test.run(|ops| async move {
+        let result = ops.partition(&["a", "b"], &["b"]).await;
+        assert!(matches!(result, Err(DockerTestError::TestBody(_))));
+    });
+}
@@ -0,0 +1,39 @@
+use crate::helper::sleep_container;
+use dockertest::{DockerTest, Source};
+use futures::StreamExt;
+use test_log::test;
+
+// Tests that a stats snapshot reports the resource usage of the container.
+#[test]
+fn test_stats_snapshot() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let stats = ops
+            .handle("sleep")
+            .stats()
+            .await
+            .expect("failed to retrieve stats");
+
+        assert!(stats.memory_usage > 0);
+        assert!(stats.memory_limit >= stats.memory_usage);
+        assert!(stats.pids >= 1);
+    });
+}
+
+// Tests that the stats stream yields multiple samples.
+#[test]
+fn test_stats_stream() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(sleep_container("sleep"));
+
+    test.run(|ops| async move {
+        let samples: Vec<_> = ops.handle("sleep").stats_stream().take(2).collect().await;
+
+        assert_eq!(samples.len(), 2);
+        for sample in samples {
+            sample.expect("failed to retrieve stats");
+        }
+    });
+}
@@ -1,14 +1,20 @@
 use dockertest::utils::connect_with_local_or_tls_defaults;
 use dockertest::waitfor::{
-    async_trait, ExitedWait, MessageSource, MessageWait, RunningWait, WaitFor,
+    async_trait, AnyOf, ExecWait, ExitedWait, HealthyWait, LogMatcher, LogMatcherWait,
+    MessageSource, MessageWait, NoWait, RunningWait, TcpPortWait, WaitFor,
 };
+#[cfg(feature = "http")]
+use dockertest::waitfor::{AllOf, HttpWait, Sequence};
 use dockertest::{
-    DockerTest, DockerTestError, PendingContainer, RunningContainer, Source, StartPolicy,
-    TestBodySpecification,
+    DockerTest, DockerTestError, HealthCheck, HealthStatus, PendingContainer, RetryPolicy,
+    RunningContainer, Source, StartPolicy, TestBodySpecification,
 };
 
 use bollard::container::InspectContainerOptions;
 use futures::future::TryFutureExt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use test_log::test;
 
 #[derive(Clone, Debug)]
@@ -26,6 +32,40 @@ impl WaitFor for FailWait {
     }
 }
 
+#[derive(Clone, Debug)]
+struct HangWait {}
+
+#[async_trait]
+impl WaitFor for HangWait {
+    async fn wait_for_ready(
+        &self,
+        _container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        futures::future::pending().await
+    }
+}
+
+/// Fails the given number of times before succeeding, shared between all clones.
+#[derive(Clone, Debug)]
+struct FlakyWait {
+    failures: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl WaitFor for FlakyWait {
+    async fn wait_for_ready(
+        &self,
+        container: PendingContainer,
+    ) -> Result<RunningContainer, DockerTestError> {
+        let remaining = self.failures.load(Ordering::SeqCst);
+        if remaining > 0 {
+            self.failures.store(remaining - 1, Ordering::SeqCst);
+            return Err(DockerTestError::Startup("flaky failure".to_string()));
+        }
+        NoWait {}.wait_for_ready(container).await
+    }
+}
+
 /// Returns whether the container is in a running state.
 pub async fn is_running(id: String) -> Result<bool, DockerTestError> {
     let client = connect_with_local_or_tls_defaults()?;
@@ -152,6 +192,30 @@ fn test_message_wait_for_success_on_stdout() {
     });
 }
 
+// Tests that the LogMatcherWait implementation waits for multiple occurrences of a regex.
+#[test]
+fn test_log_matcher_wait_for_regex_occurrences() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let matcher = LogMatcher::regex(r"^ \d\. The Docker")
+        .expect("invalid regex")
+        .occurrences(3);
+    let hello_container =
+        TestBodySpecification::with_repository(repo).set_wait_for(Box::new(LogMatcherWait {
+            matcher,
+            source: MessageSource::Stdout,
+            timeout: 5,
+        }));
+
+    test.provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+}
+
 // Tests that the MessageWait implementation fails test when message does not occur.
 #[test]
 #[should_panic]
@@ -173,3 +237,361 @@ fn test_message_wait_for_not_found_on_stream() {
         ops.handle("hello-world");
     });
 }
+
+// Check that every failing container is reported, not only the first.
+#[test]
+#[should_panic(expected = "2 container failure(s)")]
+fn test_wait_for_multiple_failed() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let strict = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(FailWait {}))
+        .set_start_policy(StartPolicy::Strict)
+        .set_handle("strict");
+    let relaxed = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(FailWait {}))
+        .set_start_policy(StartPolicy::Relaxed)
+        .set_handle("relaxed");
+
+    test.provide_container(strict).provide_container(relaxed);
+
+    test.run(|ops| async move {
+        ops.handle("strict");
+    });
+}
+
+// Check that a hanging WaitFor is cancelled by the global startup timeout.
+#[test]
+#[should_panic(
+    expected = "container `hello-world` was not ready within the global startup timeout of 2s"
+)]
+fn test_wait_for_global_startup_timeout() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new()
+        .with_default_source(source)
+        .with_startup_timeout(Duration::from_secs(2));
+
+    let repo = "hello-world";
+    let hello_container =
+        TestBodySpecification::with_repository(repo).set_wait_for(Box::new(HangWait {}));
+
+    test.provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+}
+
+// Check that a hanging WaitFor is cancelled by the container startup timeout.
+#[test]
+#[should_panic(expected = "container `hang` failed during startup")]
+fn test_wait_for_container_startup_timeout() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let hang_container = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(HangWait {}))
+        .set_startup_timeout(Duration::from_secs(2))
+        .set_handle("hang");
+    let hello_container = TestBodySpecification::with_repository(repo);
+
+    test.provide_container(hang_container)
+        .provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hang");
+    });
+}
+
+// Check that a container failing its WaitFor is recreated until it succeeds.
+#[test]
+fn test_wait_for_retry_succeeds() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let failures = Arc::new(AtomicU32::new(2));
+    let hello_container = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(FlakyWait {
+            failures: failures.clone(),
+        }))
+        .set_retry_policy(RetryPolicy::new(3, Duration::from_millis(100)));
+
+    test.provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+
+    assert_eq!(
+        failures.load(Ordering::SeqCst),
+        0,
+        "container was not retried"
+    );
+}
+
+// Check that every failed attempt is reported once the retry policy is exhausted.
+#[test]
+#[should_panic(expected = "all 3 startup attempts failed")]
+fn test_wait_for_retry_exhausted() {
+    let source = Source::DockerHub;
+    let mut test = DockerTest::new().with_default_source(source);
+
+    let repo = "hello-world";
+    let hello_container = TestBodySpecification::with_repository(repo)
+        .set_wait_for(Box::new(FailWait {}))
+        .set_retry_policy(RetryPolicy::new(3, Duration::from_millis(100)));
+
+    test.provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+}
+
+fn healthcheck_container(healthcheck: HealthCheck) -> TestBodySpecification {
+    TestBodySpecification::with_repository("luca3m/sleep")
+        .set_healthcheck(
+            healthcheck
+                .with_interval(Duration::from_millis(200))
+                .with_retries(2),
+        )
+        .set_wait_for(Box::new(HealthyWait {
+            check_interval: 1,
+            max_checks: 15,
+        }))
+}
+
+// Tests that the HealthyWait implementation awaits a passing healthcheck.
+#[test]
+fn test_healthy_wait_for() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(healthcheck_container(HealthCheck::cmd(vec!["true"])));
+
+    test.run(|ops| async move {
+        let state = ops
+            .handle("luca3m/sleep")
+            .state()
+            .await
+            .expect("failed to inspect state");
+        assert_eq!(state.health, Some(HealthStatus::Healthy));
+    });
+}
+
+// Tests that the HealthyWait implementation fails once unhealthy, reporting the probe output.
+#[test]
+#[should_panic(expected = "probe exited with 1: not ready")]
+fn test_healthy_wait_for_unhealthy() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(healthcheck_container(HealthCheck::shell(
+        "echo not ready; exit 1",
+    )));
+
+    test.run(|ops| async move {
+        ops.handle("luca3m/sleep");
+    });
+}
+
+fn nginx_container(publish: bool) -> TestBodySpecification {
+    TestBodySpecification::with_repository("nginx")
+        .set_publish_all_ports(publish)
+        .set_wait_for(Box::new(TcpPortWait {
+            port: 80,
+            timeout: Duration::from_secs(30),
+            interval: Duration::from_millis(250),
+        }))
+}
+
+// Tests that the TcpPortWait implementation connects through the published host port.
+#[test]
+fn test_tcp_port_wait_for_published_port() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(nginx_container(true));
+
+    test.run(|ops| async move {
+        let (ip, port) = ops.handle("nginx").host_port_unchecked(80);
+        tokio::net::TcpStream::connect((ip, port as u16))
+            .await
+            .expect("port is not accepting connections");
+    });
+}
+
+// Tests that the TcpPortWait implementation connects through the container ip.
+#[test]
+fn test_tcp_port_wait_for_container_ip() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    test.provide_container(nginx_container(false));
+
+    test.run(|ops| async move {
+        assert_eq!(ops.handle("nginx").host_port(80), None);
+    });
+}
+
+// Tests that the TcpPortWait implementation fails when the port never accepts connections.
+#[test]
+#[should_panic(expected = "port 8080 did not accept connections")]
+fn test_tcp_port_wait_for_timeout() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let container = TestBodySpecification::with_repository("luca3m/sleep").set_wait_for(Box::new(
+        TcpPortWait {
+            port: 8080,
+            timeout: Duration::from_secs(2),
+            interval: Duration::from_millis(250),
+        },
+    ));
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        ops.handle("luca3m/sleep");
+    });
+}
+
+// Tests that the HttpWait implementation awaits the expected response body.
+#[cfg(feature = "http")]
+#[test]
+fn test_http_wait_for_body() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let nginx = TestBodySpecification::with_repository("nginx").set_wait_for(Box::new(
+        HttpWait::new(80)
+            .with_header("Accept", "text/html")
+            .with_body_regex(r"Welcome to nginx")
+            .expect("invalid regex"),
+    ));
+    test.provide_container(nginx);
+
+    test.run(|ops| async move {
+        ops.handle("nginx");
+    });
+}
+
+// Tests that the HttpWait implementation reports the last response when not satisfied.
+#[cfg(feature = "http")]
+#[test]
+#[should_panic(expected = "responded with status 404")]
+fn test_http_wait_for_unexpected_status() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let nginx = TestBodySpecification::with_repository("nginx").set_wait_for(Box::new(
+        HttpWait::new(80)
+            .with_path("/missing")
+            .with_timeout(Duration::from_secs(5)),
+    ));
+    test.provide_container(nginx);
+
+    test.run(|ops| async move {
+        ops.handle("nginx");
+    });
+}
+
+// Tests that the ExecWait implementation polls the command until it succeeds.
+#[test]
+fn test_exec_wait_for() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let container = TestBodySpecification::with_repository("luca3m/sleep")
+        .replace_cmd(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "sleep 2; touch /tmp/ready; sleep 60".to_string(),
+        ])
+        .set_wait_for(Box::new(ExecWait {
+            cmd: vec![
+                "test".to_string(),
+                "-f".to_string(),
+                "/tmp/ready".to_string(),
+            ],
+            expected_exit_code: 0,
+            interval: Duration::from_millis(250),
+            timeout: Duration::from_secs(30),
+        }));
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        let output = ops
+            .handle("luca3m/sleep")
+            .exec(vec!["test", "-f", "/tmp/ready"])
+            .await
+            .expect("failed to exec command");
+        assert!(output.success());
+    });
+}
+
+// Tests that the ExecWait implementation reports the output of the last attempt.
+#[test]
+#[should_panic(expected = "last attempt exited with 3, stdout: `not ready`")]
+fn test_exec_wait_for_timeout() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let container =
+        TestBodySpecification::with_repository("luca3m/sleep").set_wait_for(Box::new(ExecWait {
+            cmd: vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "echo not ready; exit 3".to_string(),
+            ],
+            expected_exit_code: 0,
+            interval: Duration::from_millis(250),
+            timeout: Duration::from_secs(2),
+        }));
+    test.provide_container(container);
+
+    test.run(|ops| async move {
+        ops.handle("luca3m/sleep");
+    });
+}
+
+// Tests that combinators compose conditions of the same container.
+#[cfg(feature = "http")]
+#[test]
+fn test_combinators_wait_for() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let nginx = TestBodySpecification::with_repository("nginx").set_wait_for(Box::new(
+        Sequence::new(vec![
+            Box::new(RunningWait {
+                check_interval: 1,
+                max_checks: 10,
+            }),
+            Box::new(AllOf::new(vec![
+                Box::new(TcpPortWait {
+                    port: 80,
+                    timeout: Duration::from_secs(30),
+                    interval: Duration::from_millis(250),
+                }),
+                Box::new(HttpWait::new(80)),
+            ])),
+        ])
+        .with_timeout(Duration::from_secs(60)),
+    ));
+    test.provide_container(nginx);
+
+    test.run(|ops| async move {
+        ops.handle("nginx");
+    });
+}
+
+// Tests that AnyOf is fulfilled by any of its conditions.
+#[test]
+fn test_any_of_wait_for() {
+    let mut test = DockerTest::new().with_default_source(Source::DockerHub);
+    let hello_container =
+        TestBodySpecification::with_repository("hello-world").set_wait_for(Box::new(
+            AnyOf::new(vec![
+                Box::new(MessageWait {
+                    message: "MESSAGE NOT PRESENT IN OUTPUT".to_string(),
+                    source: MessageSource::Stdout,
+                    timeout: 5,
+                }),
+                Box::new(MessageWait {
+                    message: "Hello from Docker!".to_string(),
+                    source: MessageSource::Stdout,
+                    timeout: 5,
+                }),
+            ])
+            .with_timeout(Duration::from_secs(10)),
+        ));
+    test.provide_container(hello_container);
+
+    test.run(|ops| async move {
+        ops.handle("hello-world");
+    });
+}